@@ -1,13 +1,29 @@
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyModifiers};
-use ratatui::{backend::Backend, Terminal};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{backend::Backend, layout::Rect, Terminal};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 use crate::{
-    ui, 
+    clipboard,
+    config::{Config, UiState},
+    exec,
+    keybindings::{
+        Action, KeyBindings, BATCH_ACTIONS, EDIT_ACTIONS, HELP_ACTIONS, NORMAL_ACTIONS, SEARCH_ACTIONS, TEXT_EDIT_ACTIONS,
+    },
+    profiles::{self, ProfileEntry},
+    search,
+    snapshot::{self, ApplyOutcome, ApplyResult, SnapshotEntry},
+    theme::Theme,
+    ui::{self, ListDensity},
     edit_ui,
-    launcher, 
+    launcher::{self, LaunchOutcome}, 
     settings::*, 
     settings_editor::*,
-    event::{Event, EventHandler}
+    network_editor::NetworkAdapter,
+    event::{EditorLoaded, Event, EventHandler},
+    worker::Worker,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,9 +31,42 @@ pub enum InputMode {
     Normal,
     Search,
     Edit,
+    Confirm,
+    Help,
+    Batch,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
+pub enum Confirmation {
+    /// Ask before applying an admin or destructive change
+    Apply,
+    /// The change is applied and reverts at `deadline` unless kept
+    KeepChanges { deadline: Instant },
+    /// Quitting was requested while the editor holds a value that hasn't been saved
+    DiscardAndQuit,
+}
+
+/// Display mode being tried out live from the resolution picker
+#[derive(Debug, Clone)]
+pub struct DisplayPreview {
+    /// Mode from before previewing, put back when the preview ends
+    pub original: DisplayModeSnapshot,
+    /// When the previewed mode is rolled back unless saved
+    pub deadline: Instant,
+    /// Set while the worker applies a mode, so moving on waits for it instead of queueing every
+    /// mode passed on the way
+    pub applying: bool,
+}
+
+/// How `run_app` ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppExit {
+    Quit,
+    /// Start an elevated instance once the terminal has been restored
+    RelaunchElevated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FocusArea {
     Categories,
     Items,
@@ -33,7 +82,408 @@ pub struct EditState {
     pub options: Vec<SettingOption>,
     pub selected_option_index: usize,
     pub scroll_offset: u16,
+    pub cursor_position: usize,
     pub error_message: Option<String>,
+    /// Why the chosen value may not work, from the editor's background check
+    pub warning: Option<String>,
+    pub confirmation: Option<Confirmation>,
+    pub previous_display_mode: Option<DisplayModeSnapshot>,
+    /// Set while moving through the resolution picker applies each mode live
+    pub preview: Option<DisplayPreview>,
+    /// Set until the worker delivers the current value and options
+    pub loading: bool,
+    /// Set while the worker applies the saved value, with keys ignored until it is done
+    pub saving: bool,
+    /// Advanced every tick while loading to animate the spinner
+    pub spinner_frame: usize,
+    /// Set when saving adds the value to the staged changes instead of applying it
+    pub staging: bool,
+    /// Number of leading options that repeat recently applied display modes
+    pub recent_options: usize,
+    /// A number input's value as typed, so an entry can pass through values outside the
+    /// bounds on its way to one inside them. Only clamped when saving.
+    pub number_text: String,
+    /// Set when a number input's entry falls outside its bounds, so it was or will be pinned
+    /// to the nearer one
+    pub number_clamped: bool,
+}
+
+impl EditState {
+    /// An edit session waiting for the worker to deliver the editor's value and options
+    fn loading(item_name: String, editor: Box<dyn SettingEditor>, staging: bool) -> Self {
+        Self {
+            item_name,
+            editor_type: editor.get_editor_type(),
+            editor,
+            current_value: SettingValue::String(String::new()),
+            pending_value: None,
+            options: vec![],
+            selected_option_index: 0,
+            scroll_offset: 0,
+            cursor_position: 0,
+            error_message: None,
+            warning: None,
+            confirmation: None,
+            previous_display_mode: None,
+            preview: None,
+            loading: true,
+            saving: false,
+            spinner_frame: 0,
+            staging,
+            recent_options: 0,
+            number_text: String::new(),
+            number_clamped: false,
+        }
+    }
+    
+    /// Label for a value among this editor's options
+    pub fn label_for(&self, value: &SettingValue) -> String {
+        option_label(self.editor.as_ref(), &self.options, value)
+    }
+    
+    /// Scroll the resolution picker just far enough that the selected mode and its headings fit in `visible_rows`
+    pub fn scroll_to_selection(&mut self, visible_rows: u16) {
+        if !matches!(self.editor_type, EditorType::ResolutionPicker) {
+            return;
+        }
+        let (first, selected) = edit_ui::resolution_picker_lines(&self.options, self.recent_options, self.selected_option_index);
+        let (first, selected) = (first as u16, selected as u16);
+        if first < self.scroll_offset {
+            self.scroll_offset = first;
+        } else if selected >= self.scroll_offset + visible_rows {
+            self.scroll_offset = (selected + 1).saturating_sub(visible_rows);
+        }
+    }
+    
+    /// Highlight the next or previous option and scroll it into view, returning whether the
+    /// highlight moved
+    pub fn step_option(&mut self, forward: bool, wrap: bool, visible_rows: u16) -> bool {
+        let Some(index) = step_index(self.selected_option_index, self.options.len(), forward, wrap) else {
+            return false;
+        };
+        self.selected_option_index = index;
+        self.scroll_to_selection(visible_rows);
+        true
+    }
+    
+    /// Take the highlighted option as the pending value, or switch to free-form entry for
+    /// "Custom..." style options. Returns the editor to move on to when the option picks a
+    /// target, such as a display, rather than a value.
+    pub fn choose_highlighted(&mut self) -> Option<Box<dyn SettingEditor>> {
+        let value = self.options.get(self.selected_option_index)?.value.clone();
+        if let Some(target_editor) = self.editor.select_target(&value) {
+            return Some(target_editor);
+        }
+        if let Some(entry_type) = self.editor.custom_entry_editor(&value) {
+            self.editor_type = entry_type;
+            self.pending_value = Some(SettingValue::String(String::new()));
+            self.cursor_position = 0;
+        } else {
+            self.pending_value = Some(value);
+        }
+        None
+    }
+    
+    /// Insert a character at the cursor in a text value
+    pub fn insert_char(&mut self, c: char) {
+        if let Some(SettingValue::String(text)) = &mut self.pending_value {
+            let byte_index = char_to_byte_index(text, self.cursor_position);
+            text.insert(byte_index, c);
+            self.cursor_position += 1;
+        }
+    }
+    
+    /// Remove the character immediately before the cursor
+    pub fn delete_char_before_cursor(&mut self) {
+        if self.cursor_position == 0 {
+            return;
+        }
+        if let Some(SettingValue::String(text)) = &mut self.pending_value {
+            let byte_index = char_to_byte_index(text, self.cursor_position - 1);
+            text.remove(byte_index);
+            self.cursor_position -= 1;
+        }
+    }
+    
+    /// Preset values the typed text is the start of, as (value, option label) pairs,
+    /// e.g. "1." suggests Cloudflare's "1.1.1.1,1.0.0.1". Empty until something is typed.
+    pub fn suggestions(&self) -> Vec<(&str, &str)> {
+        let Some(SettingValue::String(text)) = &self.pending_value else {
+            return Vec::new();
+        };
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let typed = text.to_lowercase();
+        self.options
+            .iter()
+            // "Custom..." entries lead back to this field rather than holding a value
+            .filter(|option| self.editor.custom_entry_editor(&option.value).is_none())
+            .filter_map(|option| match &option.value {
+                SettingValue::Selection(value) | SettingValue::String(value) => Some((value.as_str(), option.label.as_str())),
+                _ => None,
+            })
+            .filter(|(value, _)| value.to_lowercase().starts_with(&typed) && value.len() > text.len())
+            .collect()
+    }
+    
+    /// Replace the typed text with the first suggestion, leaving the cursor at its end
+    pub fn complete_suggestion(&mut self) {
+        let Some(completion) = self.suggestions().first().map(|(value, _)| value.to_string()) else {
+            return;
+        };
+        self.cursor_position = completion.chars().count();
+        self.pending_value = Some(SettingValue::String(completion));
+    }
+    
+    /// Step an integer value by `delta`, clamped to the editor's bounds
+    pub fn adjust_number(&mut self, delta: i64) {
+        if let EditorType::NumberInput { min, max } = self.editor_type {
+            let value = match self.pending_value {
+                Some(SettingValue::Integer(value)) => value,
+                _ => min.unwrap_or(0),
+            };
+            let value = value.saturating_add(delta);
+            let clamped = clamp_number(value, min, max);
+            self.set_number(clamped);
+            self.number_clamped = clamped != value;
+        }
+    }
+    
+    /// Move a slider one step toward `max` (or `min` when not `forward`), or by the editor's
+    /// fine step when `fine`. Integer sliders always move by at least 1 so fine steps don't
+    /// get stuck.
+    pub fn nudge_slider(&mut self, forward: bool, fine: bool) {
+        if let EditorType::Slider { min, max, step } = self.editor_type {
+            let adjustment = if fine { self.editor.fine_step(step) } else { step };
+            match &mut self.pending_value {
+                Some(SettingValue::Float(val)) if forward => *val = (*val + adjustment).min(max),
+                Some(SettingValue::Float(val)) => *val = (*val - adjustment).max(min),
+                Some(SettingValue::Integer(val)) => {
+                    let adjustment = (adjustment.round() as i64).max(1);
+                    *val = if forward {
+                        val.saturating_add(adjustment).min(max.floor() as i64)
+                    } else {
+                        val.saturating_sub(adjustment).max(min.ceil() as i64)
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+    
+    /// Replace a number input's value and its typed text
+    fn set_number(&mut self, value: i64) {
+        self.number_text = value.to_string();
+        self.pending_value = Some(SettingValue::Integer(value));
+        self.number_clamped = false;
+    }
+    
+    /// Take a number input's value from its typed text, noting whether it lies out of bounds
+    fn parse_number_text(&mut self) {
+        let value = self.number_text.parse().ok();
+        self.number_clamped = match (&self.editor_type, value) {
+            (EditorType::NumberInput { min, max }, Some(value)) => clamp_number(value, *min, *max) != value,
+            _ => false,
+        };
+        self.pending_value = value.map(SettingValue::Integer);
+    }
+    
+    /// Append a typed digit to a number input, ignoring digits the value can't hold
+    pub fn push_digit(&mut self, digit: u32) {
+        if let (EditorType::NumberInput { .. }, Some(digit)) = (&self.editor_type, char::from_digit(digit, 10)) {
+            let text = format!("{}{}", self.number_text, digit);
+            if text.parse::<i64>().is_ok() {
+                self.number_text = text;
+                self.parse_number_text();
+            }
+        }
+    }
+    
+    /// Drop the last typed digit of a number input, leaving no value once none are left
+    pub fn pop_digit(&mut self) {
+        if let EditorType::NumberInput { .. } = self.editor_type {
+            self.number_text.pop();
+            self.parse_number_text();
+        }
+    }
+    
+    /// Pin a typed number outside the editor's bounds to the nearer bound, before saving
+    pub fn clamp_number_entry(&mut self) {
+        if let (EditorType::NumberInput { min, max }, Some(SettingValue::Integer(value))) =
+            (&self.editor_type, &self.pending_value)
+        {
+            let clamped = clamp_number(*value, *min, *max);
+            if clamped != *value {
+                self.set_number(clamped);
+                self.number_clamped = true;
+            }
+        }
+    }
+    
+    /// The bound a number input's entry was pinned to, if it fell outside them
+    pub fn pinned_bound(&self) -> Option<i64> {
+        match (&self.editor_type, &self.pending_value) {
+            (EditorType::NumberInput { min, max }, Some(SettingValue::Integer(value))) if self.number_clamped => {
+                Some(clamp_number(*value, *min, *max))
+            }
+            _ => None,
+        }
+    }
+    
+    pub fn move_cursor_left(&mut self) {
+        self.cursor_position = self.cursor_position.saturating_sub(1);
+    }
+    
+    pub fn move_cursor_right(&mut self) {
+        if let Some(SettingValue::String(text)) = &self.pending_value {
+            if self.cursor_position < text.chars().count() {
+                self.cursor_position += 1;
+            }
+        }
+    }
+}
+
+fn clamp_number(value: i64, min: Option<i64>, max: Option<i64>) -> i64 {
+    let value = min.map_or(value, |min| value.max(min));
+    max.map_or(value, |max| value.min(max))
+}
+
+fn char_to_byte_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
+}
+
+/// Keep items without a subcategory first and gather the rest under their subcategory,
+/// groups in the order they first appear, so each heading is drawn only once
+fn group_by_subcategory(items: &mut [SettingsItem]) {
+    let mut order: Vec<Option<String>> = vec![None];
+    for item in items.iter() {
+        if !order.contains(&item.subcategory) {
+            order.push(item.subcategory.clone());
+        }
+    }
+    // Stable, so items keep their order within a group
+    items.sort_by_key(|item| order.iter().position(|group| *group == item.subcategory));
+}
+
+/// A change that can be undone by writing `previous_value` back through `editor`
+#[derive(Debug, Clone)]
+pub struct UndoRecord {
+    pub item_name: String,
+    pub editor: Box<dyn SettingEditor>,
+    pub previous_value: SettingValue,
+    pub previous_label: String,
+}
+
+/// A value saved while staging, applied with the other staged changes later
+#[derive(Debug, Clone)]
+pub struct StagedChange {
+    pub item_name: String,
+    pub editor: Box<dyn SettingEditor>,
+    pub value: SettingValue,
+    pub label: String,
+}
+
+/// Index one step forward or back in a list of `len` entries, or `None` if the
+/// cursor stays put. With `wrap`, stepping off either end lands on the other.
+fn step_index(index: usize, len: usize, forward: bool, wrap: bool) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    match (forward, wrap) {
+        (true, _) if index < last => Some(index + 1),
+        (false, _) if index > 0 => Some((index - 1).min(last)),
+        (true, true) if last > 0 => Some(0),
+        (false, true) if last > 0 => Some(last),
+        _ => None,
+    }
+}
+
+/// Whether changes through `editor` are confirmed before they are made, as those needing
+/// administrator rights or interrupting the session are. Changes that can revert themselves
+/// are confirmed after applying instead.
+fn needs_confirmation(editor: &dyn SettingEditor) -> bool {
+    editor.revert_timeout().is_none() && (editor.requires_admin() || editor.is_destructive())
+}
+
+/// Maximum number of changes kept for undo
+const UNDO_LIMIT: usize = 20;
+/// Maximum number of distinct display modes offered as recent in the resolution picker
+const RECENT_RESOLUTIONS_LIMIT: usize = 5;
+
+/// Share of the width the categories pane starts with, in percent
+const DEFAULT_CATEGORIES_WIDTH: u16 = 30;
+/// Bounds and step for resizing the categories pane
+const MIN_CATEGORIES_WIDTH: u16 = 15;
+const MAX_CATEGORIES_WIDTH: u16 = 50;
+const CATEGORIES_WIDTH_STEP: u16 = 5;
+
+/// Maximum number of searches kept in the history
+const SEARCH_HISTORY_LIMIT: usize = 50;
+
+/// How long a previewed display mode stays up after the selection last moved
+const PREVIEW_DURATION: Duration = Duration::from_secs(5);
+
+/// Time between ticks when `tick_rate_ms` isn't set in the config
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(250);
+/// Bounds for `tick_rate_ms`, since a rate of 0 would keep the input thread spinning
+const MIN_TICK_RATE_MS: u64 = 16;
+const MAX_TICK_RATE_MS: u64 = 1000;
+
+/// Time between ticks while a spinner or countdown is on screen
+const ANIMATION_TICK_RATE: Duration = Duration::from_millis(100);
+
+/// Live value of the item shown in the detail pane. Reading it can run external
+/// commands, so it is cached and only refreshed when the selection changes.
+#[derive(Debug, Clone)]
+pub struct DetailValue {
+    pub item_name: String,
+    pub status: DetailStatus,
+}
+
+#[derive(Debug, Clone)]
+pub enum DetailStatus {
+    /// The item has no inline editor to read a value from
+    NotEditable,
+    Loading,
+    Loaded(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub set_at: Instant,
+    pub is_error: bool,
+}
+
+/// Bulk application of a snapshot, from picking the file to the per-item report
+#[derive(Debug, Clone)]
+pub enum BatchState {
+    /// Choosing a snapshot file, newest first
+    Pick { files: Vec<PathBuf>, selected: usize },
+    /// Choosing a saved profile to apply
+    Profiles {
+        profiles: Vec<(String, Vec<ProfileEntry>)>,
+        selected: usize,
+    },
+    /// Typing the name of a profile to capture the current settings into
+    NameProfile { name: String },
+    /// Typing a name of the user's own for an item, where an empty one restores the original
+    Rename { item_name: String, alias: String },
+    /// Listing the intended changes until they are confirmed
+    Review {
+        source: String,
+        entries: Vec<SnapshotEntry>,
+        scroll: u16,
+    },
+    Applying { source: String },
+    Done {
+        source: String,
+        results: Vec<ApplyResult>,
+        scroll: u16,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -46,307 +496,2014 @@ pub struct App {
     pub focus_area: FocusArea,
     pub input_mode: InputMode,
     pub search_query: String,
-    pub status_message: Option<String>,
+    /// Submitted searches, oldest first, recalled with Up and Down while searching
+    search_history: Vec<String>,
+    /// Entry of `search_history` currently recalled into the query
+    history_index: Option<usize>,
+    pub status_message: Option<StatusMessage>,
+    /// How long success messages stay in the status bar
+    pub status_timeout: Duration,
+    /// How long error messages stay in the status bar
+    pub error_status_timeout: Duration,
     pub should_quit: bool,
+    pub relaunch_elevated: bool,
     pub edit_state: Option<EditState>,
+    pub batch: Option<BatchState>,
+    pub favorites: HashSet<String>,
+    /// Names the user gave items, by item name
+    pub aliases: BTreeMap<String, String>,
+    pub undo_stack: Vec<UndoRecord>,
+    /// An undo waiting to be confirmed, off the undo stack until it is
+    pub undo_confirmation: Option<UndoRecord>,
+    /// Display modes applied this session, most recent first, offered at the top of the resolution picker
+    pub recent_resolutions: Vec<SettingValue>,
+    /// Whether saving in the edit panel stages the change instead of applying it
+    pub staging: bool,
+    /// Changes waiting to be applied together, at most one per item
+    pub staged: Vec<StagedChange>,
+    /// Set once quitting has warned about unapplied staged changes, so a second quit goes through
+    staged_quit_warned: bool,
+    pub keybindings: KeyBindings,
+    pub theme: Theme,
+    pub show_details: bool,
+    /// Share of the width, in percent, given to the categories pane
+    pub categories_width: u16,
+    /// Whether list entries show their description under the name
+    pub density: ListDensity,
+    /// Whether state is drawn as words a screen reader reads out instead of glyphs
+    pub screen_reader: bool,
+    /// Whether Up on the first entry of a list goes to the last one and Down on the last to the first
+    pub wrap_navigation: bool,
+    /// Time between ticks while nothing is animating
+    pub tick_rate: Duration,
+    /// Whether TMWT runs as administrator, checked once at startup
+    pub elevated: bool,
+    pub detail_value: Option<DetailValue>,
+    /// Current value labels shown beside editable items in the list, by item name
+    pub item_values: HashMap<String, String>,
+    /// Items whose list value was already asked for, so failed reads aren't retried every frame
+    requested_values: HashSet<String>,
+    /// Category the list values were last read for, so entering another one refreshes them
+    values_category: Option<Category>,
+    worker: Worker,
+    /// Id of the latest editor load, so late results for closed panels are ignored
+    editor_request: u64,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(event_sender: mpsc::Sender<Event>) -> Self {
         let categories = Category::all();
         let items = SETTINGS_ITEMS.clone();
+        let config = Config::load();
+        exec::init(config.command_log);
+        let (keybindings, mut startup_errors) = KeyBindings::load();
+        let theme = match config.theme.as_deref() {
+            None => Theme::default(),
+            Some(name) => Theme::by_name(name).unwrap_or_else(|| {
+                startup_errors.push(format!("Unknown theme '{}', using dark", name));
+                Theme::default()
+            }),
+        };
+        let tick_rate = match config.tick_rate_ms {
+            None => DEFAULT_TICK_RATE,
+            Some(ms) => {
+                let clamped = ms.clamp(MIN_TICK_RATE_MS, MAX_TICK_RATE_MS);
+                if clamped != ms {
+                    startup_errors.push(format!(
+                        "tick_rate_ms {} is outside {}-{}, using {}",
+                        ms, MIN_TICK_RATE_MS, MAX_TICK_RATE_MS, clamped
+                    ));
+                }
+                Duration::from_millis(clamped)
+            }
+        };
+        
+        // Open on favorites only when there are some to show
+        let category_index = if config.favorites.is_empty() {
+            categories.iter().position(|c| *c != Category::Favorites).unwrap_or(0)
+        } else {
+            0
+        };
+        
         let mut app = Self {
             categories,
             items,
             filtered_items: vec![],
-            category_index: 0,
+            category_index,
             item_index: 0,
             focus_area: FocusArea::Categories,
             input_mode: InputMode::Normal,
             search_query: String::new(),
+            search_history: Vec::new(),
+            history_index: None,
             status_message: None,
+            status_timeout: Duration::from_secs(4),
+            error_status_timeout: Duration::from_secs(8),
             should_quit: false,
+            relaunch_elevated: false,
             edit_state: None,
+            batch: None,
+            favorites: config.favorites.into_iter().collect(),
+            aliases: config.aliases,
+            undo_stack: Vec::new(),
+            undo_confirmation: None,
+            recent_resolutions: Vec::new(),
+            staging: false,
+            staged: Vec::new(),
+            staged_quit_warned: false,
+            keybindings,
+            theme,
+            show_details: false,
+            categories_width: config
+                .categories_width
+                .map_or(DEFAULT_CATEGORIES_WIDTH, |width| width.clamp(MIN_CATEGORIES_WIDTH, MAX_CATEGORIES_WIDTH)),
+            density: config.density,
+            screen_reader: config.screen_reader,
+            wrap_navigation: config.wrap_navigation,
+            tick_rate,
+            elevated: launcher::is_elevated(),
+            detail_value: None,
+            item_values: HashMap::new(),
+            requested_values: HashSet::new(),
+            values_category: None,
+            worker: Worker::new(event_sender),
+            editor_request: 0,
         };
         app.filter_items();
+        app.restore_state(UiState::load());
+        // Adapter names differ per machine, so their editors are added once they are discovered
+        app.worker.list_adapters();
+        if !startup_errors.is_empty() {
+            app.set_error(startup_errors.join(" | "));
+        }
         app
     }
+    
+    pub fn set_status(&mut self, text: impl Into<String>) {
+        self.status_message = Some(StatusMessage {
+            text: text.into(),
+            set_at: Instant::now(),
+            is_error: false,
+        });
+    }
+    
+    /// Like `set_status`, but kept on screen for `error_status_timeout`
+    pub fn set_error(&mut self, text: impl Into<String>) {
+        self.status_message = Some(StatusMessage {
+            text: text.into(),
+            set_at: Instant::now(),
+            is_error: true,
+        });
+    }
+
+    pub fn filter_items(&mut self) {
+        self.item_index = 0;
+        // Without a valid category there's nothing to list, and indexing would panic mid-draw
+        let Some(selected_category) = self.categories.get(self.category_index).cloned() else {
+            self.filtered_items.clear();
+            return;
+        };
+        
+        if self.search_query.is_empty() {
+            self.filtered_items = self
+                .items
+                .iter()
+                .filter(|item| self.belongs_to(item, &selected_category))
+                .cloned()
+                .collect();
+            group_by_subcategory(&mut self.filtered_items);
+        } else {
+            // Searching looks through every category, best matches first
+            let matcher = search::matcher();
+            let mut scored: Vec<(i64, &SettingsItem)> = self
+                .items
+                .iter()
+                .filter_map(|item| {
+                    let alias = self.aliases.get(&item.name).map(String::as_str);
+                    search::match_score(&matcher, item, alias, &self.search_query).map(|score| (score, item))
+                })
+                .collect();
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.filtered_items = scored.into_iter().map(|(_, item)| item.clone()).collect();
+        }
+    }
+    
+    fn belongs_to(&self, item: &SettingsItem, category: &Category) -> bool {
+        match category {
+            Category::Favorites => self.favorites.contains(&item.name),
+            category => &item.category == category,
+        }
+    }
+    
+    /// How many items each category holds, counting only search matches while a search is active
+    pub fn category_counts(&self) -> Vec<usize> {
+        let items = if self.search_query.is_empty() {
+            &self.items
+        } else {
+            &self.filtered_items
+        };
+        self.categories
+            .iter()
+            .map(|category| items.iter().filter(|item| self.belongs_to(item, category)).count())
+            .collect()
+    }
+    
+    /// Put the cursor back where `state` says it was, keeping the defaults for names that no longer exist
+    fn restore_state(&mut self, state: UiState) {
+        self.search_history = state.search_history;
+        
+        let Some(category_index) = state
+            .category
+            .and_then(|category| self.categories.iter().position(|c| *c == category))
+        else {
+            return;
+        };
+        // An emptied favorites list would leave the cursor on nothing
+        if self.categories[category_index] == Category::Favorites && self.favorites.is_empty() {
+            return;
+        }
+        self.category_index = category_index;
+        self.filter_items();
+        
+        if let Some(item_index) = state
+            .item
+            .and_then(|name| self.filtered_items.iter().position(|item| item.name == name))
+        {
+            self.item_index = item_index;
+            self.focus_area = FocusArea::Items;
+        }
+    }
+    
+    /// Add the current query to the search history, unless it just repeats the last one
+    fn remember_search(&mut self) {
+        self.history_index = None;
+        let query = self.search_query.trim();
+        if query.is_empty() || self.search_history.last().is_some_and(|last| last == query) {
+            return;
+        }
+        self.search_history.push(query.to_string());
+        if self.search_history.len() > SEARCH_HISTORY_LIMIT {
+            self.search_history.remove(0);
+        }
+    }
+    
+    /// Replace the query with an older (Up) or newer (Down) search. Going past the
+    /// newest entry clears the query again.
+    fn recall_search(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) => self.search_history.len().checked_sub(1),
+            (None, false) => return,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|&index| index < self.search_history.len()),
+        };
+        self.history_index = index;
+        self.search_query = index
+            .and_then(|index| self.search_history.get(index).cloned())
+            .unwrap_or_default();
+        self.filter_items();
+    }
+    
+    /// Remember the selected category and item for the next launch
+    fn save_state(&self) -> Result<()> {
+        UiState {
+            category: self.categories.get(self.category_index).cloned(),
+            item: self.filtered_items.get(self.item_index).map(|item| item.name.clone()),
+            search_history: self.search_history.clone(),
+        }
+        .save()
+    }
+    
+    fn save_config(&self) -> Result<()> {
+        let mut favorites: Vec<String> = self.favorites.iter().cloned().collect();
+        favorites.sort();
+        Config {
+            favorites,
+            aliases: self.aliases.clone(),
+            theme: Some(self.theme.name.to_string()),
+            wrap_navigation: self.wrap_navigation,
+            categories_width: Some(self.categories_width),
+            density: self.density,
+            // Options only set by editing the file are kept as they are
+            ..Config::load()
+        }
+        .save()
+    }
+    
+    fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        match self.save_config() {
+            Ok(()) => self.set_status(format!("Theme: {}", self.theme.name)),
+            Err(e) => self.set_error(format!("Theme changed but could not be saved: {}", e)),
+        }
+    }
+    
+    fn toggle_favorite(&mut self) {
+        let Some(item) = self.filtered_items.get(self.item_index) else {
+            return;
+        };
+        let name = item.name.clone();
+        
+        let added = self.favorites.insert(name.clone());
+        if !added {
+            self.favorites.remove(&name);
+        }
+        
+        if self.categories.get(self.category_index) == Some(&Category::Favorites) {
+            // Unfavorited items disappear from this view, so keep the selection in range
+            let index = self.item_index;
+            self.filter_items();
+            self.item_index = index.min(self.filtered_items.len().saturating_sub(1));
+        }
+        
+        match self.save_config() {
+            Err(e) => self.set_error(format!("Error: failed to save favorites: {}", e)),
+            Ok(()) if added => self.set_status(format!("★ Added {} to favorites", name)),
+            Ok(()) => self.set_status(format!("Removed {} from favorites", name)),
+        }
+    }
+    
+    /// Why the items pane is empty, for keys that act on the selected item
+    fn empty_list_message(&self) -> String {
+        if !self.search_query.is_empty() {
+            format!("No settings match '{}'", self.search_query)
+        } else if self.categories.get(self.category_index) == Some(&Category::Favorites) {
+            format!("No favorites yet - press {} on a setting to add it", self.keybindings.describe_keys(Action::Favorite))
+        } else {
+            "This category has no settings".to_string()
+        }
+    }
+    
+    fn move_down(&mut self, pane: FocusArea) {
+        self.move_selection(pane, true);
+    }
+    
+    fn move_up(&mut self, pane: FocusArea) {
+        self.move_selection(pane, false);
+    }
+    
+    fn move_selection(&mut self, pane: FocusArea, forward: bool) {
+        match pane {
+            FocusArea::Categories => {
+                if let Some(index) = step_index(self.category_index, self.categories.len(), forward, self.wrap_navigation) {
+                    self.category_index = index;
+                    self.filter_items();
+                }
+            }
+            FocusArea::Items => {
+                if let Some(index) = step_index(self.item_index, self.filtered_items.len(), forward, self.wrap_navigation) {
+                    self.item_index = index;
+                }
+            }
+        }
+    }
+    
+    /// Switch the items list between one and two rows per entry, and remember it
+    fn toggle_density(&mut self) {
+        self.density = self.density.toggled();
+        match self.save_config() {
+            Ok(()) => self.set_status(format!("List density: {}", self.density.name())),
+            Err(e) => self.set_error(format!("List density changed but could not be saved: {}", e)),
+        }
+    }
+    
+    /// Widen (or narrow) the categories pane by one step, within its bounds, and remember it
+    fn resize_categories(&mut self, wider: bool) {
+        let width = if wider {
+            self.categories_width + CATEGORIES_WIDTH_STEP
+        } else {
+            self.categories_width.saturating_sub(CATEGORIES_WIDTH_STEP)
+        };
+        let width = width.clamp(MIN_CATEGORIES_WIDTH, MAX_CATEGORIES_WIDTH);
+        if width == self.categories_width {
+            return;
+        }
+        self.categories_width = width;
+        if let Err(e) = self.save_config() {
+            self.set_error(format!("Could not save layout: {}", e));
+        }
+    }
+    
+    /// Drop the search results kept after accepting a search, showing the current category again
+    fn clear_search(&mut self) {
+        if self.search_query.is_empty() {
+            return;
+        }
+        self.search_query.clear();
+        self.filter_items();
+        self.set_status("Search cleared");
+    }
+    
+    /// Show the category at `index`, clearing any search. The items get the focus
+    /// so one can be picked straight away, unless the category is empty.
+    fn jump_to_category(&mut self, index: usize) {
+        if index >= self.categories.len() {
+            return;
+        }
+        self.category_index = index;
+        self.search_query.clear();
+        self.filter_items();
+        self.focus_area = if self.filtered_items.is_empty() {
+            FocusArea::Categories
+        } else {
+            FocusArea::Items
+        };
+    }
+    
+    /// Move the selection by as many rows as `pane` shows at terminal `size`, stopping
+    /// at either end of the list
+    fn move_page(&mut self, pane: FocusArea, forward: bool, size: Rect) {
+        let (categories_area, items_area) = ui::pane_areas(size, self);
+        // Rows inside the borders, at least one even on tiny terminals
+        let rows = |area: Rect| area.height.saturating_sub(2) as usize;
+        let (index, len, page) = match pane {
+            FocusArea::Categories => (self.category_index, self.categories.len(), rows(categories_area)),
+            FocusArea::Items => (self.item_index, self.filtered_items.len(), rows(items_area) / self.density.item_height()),
+        };
+        let Some(last) = len.checked_sub(1) else {
+            return;
+        };
+        let page = page.max(1);
+        let target = if forward {
+            (index + page).min(last)
+        } else {
+            index.saturating_sub(page)
+        };
+        match pane {
+            FocusArea::Categories if target != self.category_index => {
+                self.category_index = target;
+                self.filter_items();
+            }
+            FocusArea::Categories => {}
+            FocusArea::Items => self.item_index = target,
+        }
+    }
+    
+    fn jump_to_top(&mut self, pane: FocusArea) {
+        match pane {
+            FocusArea::Categories => {
+                if self.category_index != 0 {
+                    self.category_index = 0;
+                    self.filter_items();
+                }
+            }
+            FocusArea::Items => self.item_index = 0,
+        }
+    }
+    
+    fn jump_to_bottom(&mut self, pane: FocusArea) {
+        match pane {
+            FocusArea::Categories => {
+                let last = self.categories.len().saturating_sub(1);
+                if self.category_index != last {
+                    self.category_index = last;
+                    self.filter_items();
+                }
+            }
+            FocusArea::Items => self.item_index = self.filtered_items.len().saturating_sub(1),
+        }
+    }
+    
+    /// Re-read the detail pane's value if the pane is open and the selection moved to another item
+    pub fn refresh_details(&mut self) {
+        if !self.show_details {
+            return;
+        }
+        let Some(item) = self.filtered_items.get(self.item_index) else {
+            self.detail_value = None;
+            return;
+        };
+        if self.detail_value.as_ref().is_some_and(|detail| detail.item_name == item.name) {
+            return;
+        }
+        
+        let status = match item.editor_key.as_deref().and_then(create_editor) {
+            Some(editor) => {
+                self.worker.load_detail(item.name.clone(), editor);
+                DetailStatus::Loading
+            }
+            None => DetailStatus::NotEditable,
+        };
+        self.detail_value = Some(DetailValue {
+            item_name: item.name.clone(),
+            status,
+        });
+    }
+    
+    /// Start reading the list value of every listed editable item that wasn't read yet.
+    /// Values can change outside TMWT, so entering a category reads its items again.
+    pub fn refresh_item_values(&mut self) {
+        let category = self
+            .search_query
+            .is_empty()
+            .then(|| self.categories.get(self.category_index).cloned())
+            .flatten();
+        if category.is_some() && category != self.values_category {
+            for item in &self.filtered_items {
+                self.requested_values.remove(&item.name);
+            }
+            self.values_category = category;
+        }
+        
+        let editors: Vec<(String, Box<dyn SettingEditor>)> = self
+            .filtered_items
+            .iter()
+            .filter(|item| !self.requested_values.contains(&item.name))
+            .filter_map(|item| Some((item.name.clone(), create_editor(item.editor_key.as_deref()?)?)))
+            .collect();
+        if editors.is_empty() {
+            return;
+        }
+        self.requested_values.extend(editors.iter().map(|(name, _)| name.clone()));
+        self.worker.load_values(editors);
+    }
+    
+    /// Forget every cached value after a change, so the detail pane and list read them again
+    fn invalidate_values(&mut self) {
+        self.detail_value = None;
+        self.requested_values.clear();
+    }
+    
+    /// Make the editors kept for undo and staged changes read the system again, which
+    /// may have changed along with the values (e.g. a monitor swapped)
+    fn refresh_editor_caches(&mut self) {
+        let undo_editors = self.undo_stack.iter_mut().map(|record| &mut record.editor);
+        let staged_editors = self.staged.iter_mut().map(|change| &mut change.editor);
+        for editor in undo_editors.chain(staged_editors) {
+            editor.refresh_cache();
+        }
+    }
+    
+    fn on_detail_loaded(&mut self, item_name: String, result: Result<String, String>) {
+        // A failed read just leaves the list without a value, the detail pane shows why
+        if let Ok(value) = &result {
+            self.item_values.insert(item_name.clone(), value.clone());
+        }
+        
+        // Only keep the value if the pane still shows the item it was read for
+        if let Some(detail) = &mut self.detail_value {
+            if detail.item_name == item_name {
+                detail.status = match result {
+                    Ok(value) => DetailStatus::Loaded(value),
+                    Err(e) => DetailStatus::Failed(e),
+                };
+            }
+        }
+    }
+    
+    /// Copy the selected item's current value to the clipboard, or for items without an
+    /// editor the command that opens them
+    fn copy_selected(&mut self) {
+        let Some(item) = self.filtered_items.get(self.item_index) else {
+            self.set_error(self.empty_list_message());
+            return;
+        };
+        match item.editor_key.as_deref().and_then(create_editor).filter(|editor| editor.can_read()) {
+            // Reading can take a while, so it happens on the worker like the other reads
+            Some(editor) => {
+                self.worker.copy_value(editor);
+                self.set_status(format!("Reading {}...", item.name));
+            }
+            None => {
+                let text = launcher::command_line(&item.launch_command);
+                self.on_value_copied(clipboard::set_text(&text).map(|_| text).map_err(|e| format!("{:#}", e)));
+            }
+        }
+    }
+    
+    /// Start the editor's background check of the value just chosen, dropping the last one's warning
+    fn check_pending_value(&mut self) {
+        if let Some(edit_state) = &mut self.edit_state {
+            edit_state.warning = None;
+            if let Some(value) = edit_state.pending_value.clone() {
+                self.worker.check_value(self.editor_request, edit_state.editor.clone(), value);
+            }
+        }
+    }
+    
+    fn on_value_checked(&mut self, request: u64, value: SettingValue, warning: Option<String>) {
+        // The panel may have been closed, or another value chosen, while checking
+        if request != self.editor_request {
+            return;
+        }
+        if let Some(edit_state) = self.edit_state.as_mut().filter(|edit_state| edit_state.pending_value.as_ref() == Some(&value)) {
+            edit_state.warning = warning;
+        }
+    }
+    
+    /// Add the per-adapter items, keeping the cursor on the item it is on
+    fn on_adapters_listed(&mut self, result: Result<Vec<NetworkAdapter>, String>) {
+        let adapters = match result {
+            Ok(adapters) if adapters.is_empty() => {
+                self.set_error("No network adapters found - adapter and DNS editors are unavailable");
+                return;
+            }
+            Ok(adapters) => adapters,
+            Err(e) => {
+                self.set_error(format!("Could not list network adapters: {}", e));
+                return;
+            }
+        };
+        let network_start = self
+            .items
+            .iter()
+            .position(|item| item.category == Category::Network)
+            .unwrap_or(self.items.len());
+        self.items.splice(network_start..network_start, network_adapter_items(&adapters));
+        
+        let selected = self.filtered_items.get(self.item_index).map(|item| item.name.clone());
+        self.filter_items();
+        if let Some(index) = selected.and_then(|name| self.filtered_items.iter().position(|item| item.name == name)) {
+            self.item_index = index;
+        }
+    }
+    
+    fn on_value_copied(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(text) => self.set_status(format!("Copied: {}", text)),
+            Err(e) => self.set_error(format!("Copy failed: {}", e)),
+        }
+    }
+    
+    /// Write every editable setting's current value to a JSON file, off the UI thread
+    fn export_snapshot(&mut self) {
+        self.worker.export_snapshot(self.items.clone());
+        self.set_status("Exporting current settings...");
+    }
+    
+    fn start_import(&mut self) {
+        let files = snapshot::list_snapshots();
+        if files.is_empty() {
+            let dir = snapshot::snapshots_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+            self.set_error(format!(
+                "No snapshots found in {} - export one with {} first",
+                dir,
+                self.keybindings.describe_keys(Action::Export)
+            ));
+            return;
+        }
+        self.batch = Some(BatchState::Pick { files, selected: 0 });
+        self.input_mode = InputMode::Batch;
+    }
+    
+    /// Profile picker listing every saved profile, or `None` if they couldn't be read
+    fn profile_picker(&mut self) -> Option<BatchState> {
+        match profiles::load() {
+            Ok(profiles) => Some(BatchState::Profiles {
+                profiles: profiles.into_iter().collect(),
+                selected: 0,
+            }),
+            Err(e) => {
+                self.set_error(format!("Failed to load profiles: {}", e));
+                None
+            }
+        }
+    }
+    
+    /// Name `item` is listed under, the user's alias for it if they gave one
+    pub fn display_name<'a>(&'a self, item: &'a SettingsItem) -> &'a str {
+        self.aliases.get(&item.name).map_or(&item.name, String::as_str)
+    }
+    
+    /// Ask for a new name for the selected item, starting from its current alias
+    fn start_rename(&mut self) {
+        let Some(item) = self.filtered_items.get(self.item_index) else {
+            self.set_error(self.empty_list_message());
+            return;
+        };
+        self.batch = Some(BatchState::Rename {
+            item_name: item.name.clone(),
+            alias: self.aliases.get(&item.name).cloned().unwrap_or_default(),
+        });
+        self.input_mode = InputMode::Batch;
+    }
+    
+    /// Show `item_name` as `alias` from now on, or under its own name again if `alias` is empty
+    fn rename_item(&mut self, item_name: String, alias: &str) {
+        let alias = alias.trim();
+        let message = if alias.is_empty() || alias == item_name {
+            self.aliases.remove(&item_name);
+            format!("{} shows under its own name again", item_name)
+        } else {
+            self.aliases.insert(item_name.clone(), alias.to_string());
+            format!("{} now shows as {}", item_name, alias)
+        };
+        if !self.search_query.is_empty() {
+            // The alias may change what matches, so keep the cursor on the renamed item
+            self.filter_items();
+            self.item_index = self.filtered_items.iter().position(|item| item.name == item_name).unwrap_or(0);
+        }
+        match self.save_config() {
+            Ok(()) => self.set_status(message),
+            Err(e) => self.set_error(format!("Renamed, but the name could not be saved: {}", e)),
+        }
+    }
+    
+    fn start_profiles(&mut self) {
+        self.batch = self.profile_picker();
+        if self.batch.is_some() {
+            self.input_mode = InputMode::Batch;
+        }
+    }
+    
+    fn handle_batch_key(&mut self, key: KeyEvent) {
+        let action = self.keybindings.action_for(&key, BATCH_ACTIONS);
+        let Some(batch) = self.batch.take() else {
+            return;
+        };
+        
+        self.batch = match batch {
+            BatchState::Pick { files, selected } => match action {
+                Some(Action::MoveUp) => Some(BatchState::Pick {
+                    selected: selected.saturating_sub(1),
+                    files,
+                }),
+                Some(Action::MoveDown) => Some(BatchState::Pick {
+                    selected: (selected + 1).min(files.len().saturating_sub(1)),
+                    files,
+                }),
+                Some(Action::Select) => {
+                    let path = &files[selected];
+                    match snapshot::load(path) {
+                        Ok(entries) => Some(BatchState::Review {
+                            source: path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default(),
+                            entries,
+                            scroll: 0,
+                        }),
+                        Err(e) => {
+                            self.set_error(format!("Import failed: {}", e));
+                            Some(BatchState::Pick { files, selected })
+                        }
+                    }
+                }
+                Some(Action::Cancel) => None,
+                _ => Some(BatchState::Pick { files, selected }),
+            },
+            BatchState::Review { source, entries, scroll } => match (key.code, action) {
+                // Nothing changes until the summary has been confirmed
+                (KeyCode::Char('y') | KeyCode::Char('Y'), _) => {
+                    self.worker.apply_snapshot(entries);
+                    Some(BatchState::Applying { source })
+                }
+                (KeyCode::Char('n') | KeyCode::Char('N'), _) | (_, Some(Action::Cancel)) => None,
+                (_, Some(Action::MoveUp)) => Some(BatchState::Review {
+                    source,
+                    entries,
+                    scroll: scroll.saturating_sub(1),
+                }),
+                (_, Some(Action::MoveDown)) => Some(BatchState::Review {
+                    source,
+                    entries,
+                    scroll: scroll + 1,
+                }),
+                _ => Some(BatchState::Review { source, entries, scroll }),
+            },
+            BatchState::Profiles { profiles, selected } => match action {
+                Some(Action::MoveUp) => Some(BatchState::Profiles {
+                    selected: selected.saturating_sub(1),
+                    profiles,
+                }),
+                Some(Action::MoveDown) => Some(BatchState::Profiles {
+                    selected: (selected + 1).min(profiles.len().saturating_sub(1)),
+                    profiles,
+                }),
+                Some(Action::Select) if !profiles.is_empty() => {
+                    let (name, entries) = &profiles[selected];
+                    // Applied without a review, unlike an import - the profile was set up on purpose
+                    self.worker.apply_snapshot(profiles::to_snapshot_entries(entries, &self.items));
+                    Some(BatchState::Applying { source: name.clone() })
+                }
+                Some(Action::CaptureProfile) => Some(BatchState::NameProfile { name: String::new() }),
+                Some(Action::Cancel) => None,
+                _ => Some(BatchState::Profiles { profiles, selected }),
+            },
+            // Typed text can't go through BATCH_ACTIONS, which binds letters and Space
+            BatchState::NameProfile { mut name } => match self.keybindings.action_for(&key, SEARCH_ACTIONS) {
+                Some(Action::Accept) => {
+                    let name = name.trim().to_string();
+                    if name.is_empty() {
+                        Some(BatchState::NameProfile { name })
+                    } else {
+                        self.set_status(format!("Capturing current settings into '{}'...", name));
+                        self.worker.capture_profile(name, self.items.clone());
+                        None
+                    }
+                }
+                Some(Action::Cancel) => self.profile_picker(),
+                _ => {
+                    match key.code {
+                        KeyCode::Backspace => {
+                            name.pop();
+                        }
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => name.push(c),
+                        _ => {}
+                    }
+                    Some(BatchState::NameProfile { name })
+                }
+            },
+            BatchState::Rename { item_name, mut alias } => match self.keybindings.action_for(&key, SEARCH_ACTIONS) {
+                Some(Action::Accept) => {
+                    self.rename_item(item_name, &alias);
+                    None
+                }
+                Some(Action::Cancel) => None,
+                _ => {
+                    match key.code {
+                        KeyCode::Backspace => {
+                            alias.pop();
+                        }
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => alias.push(c),
+                        _ => {}
+                    }
+                    Some(BatchState::Rename { item_name, alias })
+                }
+            },
+            // A half-applied batch can't be cancelled
+            applying @ BatchState::Applying { .. } => Some(applying),
+            BatchState::Done { source, results, scroll } => match action {
+                Some(Action::Cancel | Action::Select) => None,
+                Some(Action::MoveUp) => Some(BatchState::Done {
+                    source,
+                    results,
+                    scroll: scroll.saturating_sub(1),
+                }),
+                Some(Action::MoveDown) => Some(BatchState::Done {
+                    source,
+                    results,
+                    scroll: scroll + 1,
+                }),
+                _ => Some(BatchState::Done { source, results, scroll }),
+            },
+        };
+        
+        if self.batch.is_none() {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+    
+    fn on_batch_applied(&mut self, results: Vec<ApplyResult>) {
+        let Some(BatchState::Applying { source }) = self.batch.take() else {
+            return;
+        };
+        
+        let count = |matches: fn(&ApplyOutcome) -> bool| results.iter().filter(|result| matches(&result.outcome)).count();
+        let failed = count(|outcome| matches!(outcome, ApplyOutcome::Failed(_)));
+        let summary = format!(
+            "{}: {} changed, {} already set, {} skipped, {} failed",
+            source,
+            count(|outcome| matches!(outcome, ApplyOutcome::Changed)),
+            count(|outcome| matches!(outcome, ApplyOutcome::Unchanged)),
+            count(|outcome| matches!(outcome, ApplyOutcome::Skipped(_))),
+            failed,
+        );
+        if failed > 0 {
+            self.set_error(summary);
+        } else {
+            self.set_status(summary);
+        }
+        
+        self.batch = Some(BatchState::Done {
+            source,
+            results,
+            scroll: 0,
+        });
+        self.invalidate_values();
+    }
+    
+    fn handle_mouse(&mut self, mouse: MouseEvent, size: Rect) {
+        let (categories_area, items_area) = ui::pane_areas(size, self);
+        let contains = |area: Rect| {
+            mouse.column >= area.x
+                && mouse.column < area.x + area.width
+                && mouse.row >= area.y
+                && mouse.row < area.y + area.height
+        };
+        let pane = if contains(categories_area) {
+            FocusArea::Categories
+        } else if contains(items_area) {
+            FocusArea::Items
+        } else {
+            return;
+        };
+        
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.move_down(pane),
+            MouseEventKind::ScrollUp => self.move_up(pane),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.focus_area = pane;
+                match pane {
+                    FocusArea::Categories => {
+                        // Rows start inside the top border
+                        let row = mouse.row.saturating_sub(categories_area.y + 1) as usize;
+                        if mouse.row > categories_area.y && row < self.categories.len() && row != self.category_index {
+                            self.category_index = row;
+                            self.filter_items();
+                        }
+                    }
+                    FocusArea::Items => {
+                        if let Some(index) = self.item_at(mouse, items_area) {
+                            self.item_index = index;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    /// Index in `filtered_items` of the item drawn under the mouse, if any
+    fn item_at(&self, mouse: MouseEvent, items_area: Rect) -> Option<usize> {
+        let inside = mouse.column >= items_area.x
+            && mouse.column < items_area.x + items_area.width
+            && mouse.row > items_area.y
+            && mouse.row < items_area.y + items_area.height;
+        if !inside {
+            return None;
+        }
+        // Rows start inside the top border
+        let row = mouse.row.saturating_sub(items_area.y + 1) as usize;
+        ui::item_at_row(self, items_area, row)
+    }
+    
+    /// Open the editor of the item double-clicked, which the first click already selected
+    fn handle_double_click(&mut self, mouse: MouseEvent, size: Rect) {
+        let (_, items_area) = ui::pane_areas(size, self);
+        if self.item_at(mouse, items_area) != Some(self.item_index) {
+            return;
+        }
+        if let Some(item) = self.filtered_items.get(self.item_index).cloned() {
+            if item.can_edit_inline {
+                if let Err(e) = self.enter_edit_mode(&item) {
+                    self.set_error(format!("Error: {}", e));
+                }
+            }
+        }
+    }
+    
+    fn enter_edit_mode(&mut self, item: &SettingsItem) -> Result<()> {
+        if let Some(editor_key) = &item.editor_key {
+            if let Some(editor) = create_editor(editor_key) {
+                self.open_editor(item.name.clone(), editor);
+                Ok(())
+            } else {
+                anyhow::bail!("No editor available for this setting")
+            }
+        } else {
+            anyhow::bail!("This setting cannot be edited inline")
+        }
+    }
+    
+    /// Show the edit panel in its loading state while the worker fetches the value and options
+    fn open_editor(&mut self, item_name: String, editor: Box<dyn SettingEditor>) {
+        self.editor_request += 1;
+        self.worker.load_editor(self.editor_request, editor.clone());
+        
+        self.edit_state = Some(EditState::loading(item_name, editor, self.staging));
+        self.input_mode = InputMode::Edit;
+    }
+    
+    fn on_editor_loaded(&mut self, loaded: EditorLoaded) {
+        let Some(edit_state) = &mut self.edit_state else {
+            return;
+        };
+        if !edit_state.loading || loaded.request != self.editor_request {
+            return;
+        }
+        
+        let (current_value, options) = match loaded.result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.edit_state = None;
+                self.input_mode = InputMode::Normal;
+                self.set_error(format!("Error: {}", e));
+                return;
+            }
+        };
+        
+        // Skip the target selection step when there is only one target to pick
+        if let [only] = options.as_slice() {
+            if let Some(target_editor) = edit_state.editor.select_target(&only.value) {
+                let item_name = edit_state.item_name.clone();
+                self.open_editor(item_name, target_editor);
+                return;
+            }
+        }
+        
+        // Modes applied this session come first, as long as the display still supports them
+        let mut options = options;
+        if matches!(edit_state.editor_type, EditorType::ResolutionPicker) {
+            let recent: Vec<SettingOption> = self
+                .recent_resolutions
+                .iter()
+                .filter_map(|value| options.iter().find(|option| &option.value == value).cloned())
+                .collect();
+            edit_state.recent_options = recent.len();
+            options.splice(0..0, recent);
+        }
+        
+        // Start on the option matching the current value so it's obvious what is active
+        let selected_option_index = options
+            .iter()
+            .position(|option| option.value == current_value)
+            .unwrap_or(0);
+        edit_state.cursor_position = match &current_value {
+            SettingValue::String(text) => text.chars().count(),
+            _ => 0,
+        };
+        // Open the resolution picker with the active mode at the top
+        if matches!(edit_state.editor_type, EditorType::ResolutionPicker) {
+            edit_state.scroll_offset =
+                edit_ui::resolution_picker_lines(&options, edit_state.recent_options, selected_option_index).0 as u16;
+        }
+        if let SettingValue::Integer(value) = current_value {
+            edit_state.number_text = value.to_string();
+        }
+        edit_state.selected_option_index = selected_option_index;
+        edit_state.options = options;
+        edit_state.current_value = current_value.clone();
+        edit_state.pending_value = Some(current_value);
+        edit_state.loading = false;
+    }
+    
+    fn validated_pending_value(&self) -> Result<Option<SettingValue>> {
+        if let Some(edit_state) = &self.edit_state {
+            if let Some(pending_value) = &edit_state.pending_value {
+                if edit_state.editor.validate_value(pending_value)? {
+                    Ok(Some(pending_value.clone()))
+                } else {
+                    anyhow::bail!("{} is not a valid value", edit_state.label_for(pending_value))
+                }
+            } else {
+                anyhow::bail!("No value to save")
+            }
+        } else {
+            Ok(None)
+        }
+    }
+    
+    /// Have the worker validate and apply the pending value, finishing in `on_value_saved`
+    fn save_edit(&mut self) -> Result<()> {
+        let Some(edit_state) = &mut self.edit_state else {
+            return Ok(());
+        };
+        let Some(value) = edit_state.pending_value.clone() else {
+            anyhow::bail!("No value to save");
+        };
+        // While previewing, the display already shows a trial mode rather than the real one
+        let capture = edit_state.preview.is_none();
+        edit_state.saving = true;
+        self.worker.save_value(
+            self.editor_request,
+            edit_state.editor.clone(),
+            value.clone(),
+            edit_state.label_for(&value),
+            capture,
+        );
+        Ok(())
+    }
+    
+    fn on_value_saved(&mut self, request: u64, result: Result<Option<Box<DisplayModeSnapshot>>, String>) {
+        if request != self.editor_request {
+            return;
+        }
+        let Some(edit_state) = self.edit_state.as_mut().filter(|edit_state| edit_state.saving) else {
+            return;
+        };
+        edit_state.saving = false;
+        let previous_display_mode = match result {
+            Ok(previous_display_mode) => previous_display_mode,
+            Err(e) => {
+                self.report_save_error(anyhow::anyhow!(e));
+                return;
+            }
+        };
+        edit_state.previous_display_mode = match edit_state.preview.take() {
+            Some(preview) => Some(preview.original),
+            None => previous_display_mode.map(|snapshot| *snapshot),
+        };
+        
+        if edit_state.editor.supports_undo() {
+            // The value the panel loaded with, rather than reading the setting a second time
+            let previous_value = edit_state.current_value.clone();
+            self.undo_stack.push(UndoRecord {
+                item_name: edit_state.item_name.clone(),
+                editor: edit_state.editor.clone(),
+                previous_label: edit_state.label_for(&previous_value),
+                previous_value,
+            });
+            if self.undo_stack.len() > UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+        }
+        
+        if let Some(timeout) = edit_state.editor.revert_timeout() {
+            // Keep the editor open so the change can still be reverted
+            edit_state.confirmation = Some(Confirmation::KeepChanges {
+                deadline: Instant::now() + timeout,
+            });
+            self.input_mode = InputMode::Confirm;
+        } else {
+            self.finish_edit();
+        }
+    }
+    
+    fn try_save_edit(&mut self) {
+        let blocked = self
+            .edit_state
+            .as_ref()
+            .filter(|edit_state| edit_state.editor.requires_admin() && !self.elevated)
+            .map(|edit_state| edit_state.item_name.clone());
+        if let Some(item_name) = blocked {
+            self.report_save_error(anyhow::anyhow!(
+                "{} needs administrator rights - press {} to relaunch TMWT as administrator",
+                item_name,
+                self.keybindings.describe_keys(Action::RelaunchElevated)
+            ));
+            return;
+        }
+        
+        let read_only = self
+            .edit_state
+            .as_ref()
+            .filter(|edit_state| !edit_state.editor.can_write())
+            .map(|edit_state| edit_state.item_name.clone());
+        if let Some(item_name) = read_only {
+            self.report_save_error(anyhow::anyhow!("{} can't be changed from TMWT yet", item_name));
+            return;
+        }
+        
+        if let Some(edit_state) = &mut self.edit_state {
+            edit_state.clamp_number_entry();
+        }
+        
+        // Staged changes are confirmed as they are staged, since applying them all at once asks nothing
+        let confirm_first = self
+            .edit_state
+            .as_ref()
+            .is_some_and(|edit_state| needs_confirmation(edit_state.editor.as_ref()));
+        
+        let result = if confirm_first {
+            // The value is validated once confirmed, on the worker
+            match &mut self.edit_state {
+                Some(edit_state) if edit_state.pending_value.is_none() => Err(anyhow::anyhow!("No value to save")),
+                Some(edit_state) => {
+                    edit_state.confirmation = Some(Confirmation::Apply);
+                    self.input_mode = InputMode::Confirm;
+                    Ok(())
+                }
+                None => Ok(()),
+            }
+        } else if self.staging {
+            self.stage_edit()
+        } else {
+            self.save_edit()
+        };
+        
+        if let Err(e) = result {
+            self.report_save_error(e);
+        }
+    }
+    
+    /// Add the pending value to the staged changes and close the panel, replacing an
+    /// earlier staged change to the same item
+    fn stage_edit(&mut self) -> Result<()> {
+        let Some(value) = self.validated_pending_value()? else {
+            return Ok(());
+        };
+        let Some(edit_state) = &self.edit_state else {
+            return Ok(());
+        };
+        if edit_state.editor.revert_timeout().is_some() {
+            anyhow::bail!(
+                "{} has to be confirmed on screen, so it can't be staged - turn staging off with {} to save it",
+                edit_state.item_name,
+                self.keybindings.describe_keys(Action::ToggleStaging)
+            );
+        }
+        
+        let change = StagedChange {
+            item_name: edit_state.item_name.clone(),
+            editor: edit_state.editor.clone(),
+            label: edit_state.label_for(&value),
+            value,
+        };
+        self.staged.retain(|staged| staged.item_name != change.item_name);
+        self.set_status(format!(
+            "Staged {} → {} ({} staged, {} applies them)",
+            change.item_name,
+            change.label,
+            self.staged.len() + 1,
+            self.keybindings.describe_keys(Action::ApplyStaged)
+        ));
+        self.staged.push(change);
+        self.edit_state = None;
+        self.input_mode = InputMode::Normal;
+        Ok(())
+    }
+    
+    fn toggle_staging(&mut self) {
+        self.staging = !self.staging;
+        if self.staging {
+            self.set_status(format!(
+                "Staging on - saved changes wait until {} applies them",
+                self.keybindings.describe_keys(Action::ApplyStaged)
+            ));
+        } else if self.staged.is_empty() {
+            self.set_status("Staging off - changes are applied when saved");
+        } else {
+            self.set_status(format!(
+                "Staging off - {} staged changes still wait for {}",
+                self.staged.len(),
+                self.keybindings.describe_keys(Action::ApplyStaged)
+            ));
+        }
+    }
+    
+    /// Apply every staged change in the background, reporting them like an imported snapshot
+    fn apply_staged(&mut self) {
+        if self.staged.is_empty() {
+            self.set_status(format!(
+                "Nothing staged - press {} to stage changes instead of saving them",
+                self.keybindings.describe_keys(Action::ToggleStaging)
+            ));
+            return;
+        }
+        self.worker.apply_staged(std::mem::take(&mut self.staged));
+        self.batch = Some(BatchState::Applying {
+            source: "Staged changes".to_string(),
+        });
+        self.input_mode = InputMode::Batch;
+    }
+    
+    fn report_save_error(&mut self, e: anyhow::Error) {
+        if let Some(edit_state) = &mut self.edit_state {
+            edit_state.error_message = Some(e.to_string());
+        }
+        self.set_error(format!("Save failed: {}", e));
+    }
+    
+    fn confirm_yes(&mut self) {
+        if let Some(record) = self.undo_confirmation.take() {
+            self.input_mode = InputMode::Normal;
+            self.start_undo(record);
+            return;
+        }
+        
+        let confirmation = self.edit_state.as_mut().and_then(|edit_state| edit_state.confirmation.take());
+        match confirmation {
+            Some(Confirmation::Apply) => {
+                self.input_mode = InputMode::Edit;
+                let result = if self.staging { self.stage_edit() } else { self.save_edit() };
+                if let Err(e) = result {
+                    self.report_save_error(e);
+                }
+            }
+            Some(Confirmation::KeepChanges { .. }) => self.finish_edit(),
+            Some(Confirmation::DiscardAndQuit) => {
+                // Don't leave a previewed display mode behind
+                self.end_preview();
+                self.should_quit = true;
+            }
+            None => {}
+        }
+    }
+    
+    fn confirm_no(&mut self) {
+        if let Some(record) = self.undo_confirmation.take() {
+            // Still there to undo later
+            self.undo_stack.push(record);
+            self.input_mode = InputMode::Normal;
+            self.set_status("Change not undone");
+            return;
+        }
+        
+        let confirmation = self.edit_state.as_mut().and_then(|edit_state| edit_state.confirmation.take());
+        match confirmation {
+            Some(Confirmation::Apply) => {
+                self.input_mode = InputMode::Edit;
+                self.set_status(if self.staging { "Change not staged" } else { "Change not applied" });
+            }
+            Some(Confirmation::KeepChanges { .. }) => self.revert_edit(),
+            Some(Confirmation::DiscardAndQuit) => self.input_mode = InputMode::Edit,
+            None => {}
+        }
+    }
+    
+    /// Restore the value the setting had before editing
+    fn revert_edit(&mut self) {
+        if let Some(edit_state) = self.edit_state.take() {
+            // The applied change is being rolled back, so it no longer needs undoing
+            if edit_state.editor.supports_undo() {
+                self.undo_stack.pop();
+            }
 
-    pub fn filter_items(&mut self) {
-        let selected_category = &self.categories[self.category_index];
-        self.filtered_items = self
-            .items
-            .iter()
-            .filter(|item| {
-                let category_match = &item.category == selected_category;
-                let search_match = if self.search_query.is_empty() {
-                    true
-                } else {
-                    item.name
-                        .to_lowercase()
-                        .contains(&self.search_query.to_lowercase())
-                        || item
-                            .description
-                            .as_deref()
-                            .unwrap_or("")
-                            .to_lowercase()
-                            .contains(&self.search_query.to_lowercase())
-                        || item
-                            .keywords
-                            .iter()
-                            .any(|k| k.to_lowercase().contains(&self.search_query.to_lowercase()))
-                };
-                category_match && search_match
-            })
-            .cloned()
-            .collect();
-        self.item_index = 0;
+            let label = edit_state.label_for(&edit_state.current_value);
+            let result = match &edit_state.previous_display_mode {
+                Some(snapshot) => snapshot.restore(),
+                None => edit_state.editor.set_value(edit_state.current_value.clone()),
+            };
+            match result {
+                Ok(()) => self.set_status(format!("↺ {} reverted to {}", edit_state.item_name, label)),
+                Err(e) => self.set_error(format!("Error: failed to revert {}: {}", edit_state.item_name, e)),
+            }
+        }
+        self.invalidate_values();
+        self.input_mode = InputMode::Normal;
     }
     
-    fn enter_edit_mode(&mut self, item: &SettingsItem) -> Result<()> {
-        if let Some(editor_key) = &item.editor_key {
-            if let Some(editor) = create_editor(editor_key) {
-                let current_value = editor.get_current_value()?;
-                let options = editor.get_available_options()?;
-                let editor_type = editor.get_editor_type();
-                
-                self.edit_state = Some(EditState {
-                    item_name: item.name.clone(),
-                    editor,
-                    editor_type,
-                    current_value: current_value.clone(),
-                    pending_value: Some(current_value),
-                    options,
-                    selected_option_index: 0,
-                    scroll_offset: 0,
-                    error_message: None,
-                });
-                
-                self.input_mode = InputMode::Edit;
-                Ok(())
+    fn undo_last_change(&mut self) {
+        let Some(record) = self.undo_stack.pop() else {
+            self.set_status("Nothing to undo");
+            return;
+        };
+        
+        if needs_confirmation(record.editor.as_ref()) {
+            self.undo_confirmation = Some(record);
+            self.input_mode = InputMode::Confirm;
+        } else {
+            self.start_undo(record);
+        }
+    }
+    
+    fn start_undo(&mut self, record: UndoRecord) {
+        self.set_status(format!("Restoring {} to {}...", record.item_name, record.previous_label));
+        self.worker.undo_change(record);
+    }
+    
+    fn on_change_undone(&mut self, item_name: String, label: String, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.set_status(format!("↶ {} restored to {}", item_name, label)),
+            Err(e) => self.set_error(format!("Error: failed to undo {}: {}", item_name, e)),
+        }
+        self.invalidate_values();
+    }
+    
+    fn finish_edit(&mut self) {
+        if let Some(edit_state) = self.edit_state.take() {
+            let mut message = if exec::is_dry_run() {
+                let pending = edit_state.pending_value.as_ref().map(|value| edit_state.label_for(value));
+                format!(
+                    "[DRY RUN] {} would be set to {}",
+                    edit_state.item_name,
+                    pending.unwrap_or_default()
+                )
+            } else if let Some(note) = edit_state.editor.apply_note() {
+                format!("✓ {} updated - {}", edit_state.item_name, note)
             } else {
-                anyhow::bail!("No editor available for this setting")
+                format!("✓ {} updated successfully", edit_state.item_name)
+            };
+            let requires_restart = edit_state.editor.requires_restart()
+                || self
+                    .items
+                    .iter()
+                    .any(|item| item.name == edit_state.item_name && item.requires_restart);
+            if requires_restart {
+                message.push_str(" (restart required)");
+            }
+            self.set_status(message);
+            if let Some(mode @ SettingValue::Resolution { .. }) = edit_state.pending_value {
+                self.remember_resolution(mode);
             }
-        } else {
-            anyhow::bail!("This setting cannot be edited inline")
         }
+        // Cached values are stale after a change
+        self.invalidate_values();
+        self.input_mode = InputMode::Normal;
     }
     
-    fn save_edit(&mut self) -> Result<()> {
-        if let Some(edit_state) = &self.edit_state {
-            if let Some(pending_value) = &edit_state.pending_value {
-                // Validate before saving
-                if edit_state.editor.validate_value(pending_value)? {
-                    edit_state.editor.set_value(pending_value.clone())?;
-                    self.status_message = Some(format!("✓ {} updated successfully", edit_state.item_name));
-                    self.input_mode = InputMode::Normal;
-                    self.edit_state = None;
-                    Ok(())
-                } else {
-                    anyhow::bail!("Invalid value")
-                }
+    /// Put an applied display mode at the front of the recent ones, dropping the oldest past the limit
+    fn remember_resolution(&mut self, mode: SettingValue) {
+        self.recent_resolutions.retain(|recent| *recent != mode);
+        self.recent_resolutions.insert(0, mode);
+        self.recent_resolutions.truncate(RECENT_RESOLUTIONS_LIMIT);
+    }
+    
+    /// Whether something on screen changes by itself, like the loading spinner or a
+    /// revert countdown, and so needs fast ticks
+    fn is_animating(&self) -> bool {
+        self.edit_state.as_ref().is_some_and(|edit_state| {
+            edit_state.loading
+                || edit_state.preview.is_some()
+                || matches!(edit_state.confirmation, Some(Confirmation::KeepChanges { .. }))
+        })
+    }
+    
+    /// Advance spinners and timers, returning whether anything on screen changed
+    fn on_tick(&mut self) -> bool {
+        // Spinners and countdowns move on every tick
+        let mut changed = self.is_animating();
+        
+        // Let old messages expire so the help text comes back
+        if let Some(message) = &self.status_message {
+            let timeout = if message.is_error {
+                self.error_status_timeout
             } else {
-                anyhow::bail!("No value to save")
+                self.status_timeout
+            };
+            if message.set_at.elapsed() >= timeout {
+                self.status_message = None;
+                changed = true;
+            }
+        }
+        
+        if let Some(edit_state) = self.edit_state.as_mut().filter(|edit_state| edit_state.loading) {
+            edit_state.spinner_frame = edit_state.spinner_frame.wrapping_add(1);
+        }
+        
+        let expired = matches!(
+            self.edit_state.as_ref().and_then(|edit_state| edit_state.confirmation.as_ref()),
+            Some(Confirmation::KeepChanges { deadline }) if Instant::now() >= *deadline
+        );
+        if expired {
+            self.revert_edit();
+        }
+        
+        // A mode being saved stays, so the preview isn't ended under it
+        let preview_expired = self
+            .edit_state
+            .as_ref()
+            .filter(|edit_state| !edit_state.saving)
+            .and_then(|edit_state| edit_state.preview.as_ref())
+            .is_some_and(|preview| Instant::now() >= preview.deadline);
+        if preview_expired {
+            self.end_preview();
+            self.set_status("Preview ended - display mode restored");
+        }
+        
+        changed
+    }
+    
+    /// Quit, unless an edit with an unsaved value is open, in which case ask first
+    fn request_quit(&mut self) {
+        let unsaved = self.edit_state.as_mut().filter(|edit_state| {
+            !edit_state.loading && edit_state.pending_value.as_ref().is_some_and(|value| value != &edit_state.current_value)
+        });
+        match unsaved {
+            Some(edit_state) => {
+                edit_state.confirmation = Some(Confirmation::DiscardAndQuit);
+                self.input_mode = InputMode::Confirm;
+            }
+            // Staged changes only live in memory, so quitting loses them
+            None if !self.staged.is_empty() && !self.staged_quit_warned => {
+                self.staged_quit_warned = true;
+                self.set_error(format!(
+                    "{} staged changes haven't been applied - press {} to apply them, or {} again to quit without them",
+                    self.staged.len(),
+                    self.keybindings.describe_keys(Action::ApplyStaged),
+                    self.keybindings.describe_keys(Action::Quit)
+                ));
+            }
+            None => {
+                self.end_preview();
+                self.should_quit = true;
             }
-        } else {
-            Ok(())
         }
     }
     
     fn cancel_edit(&mut self) {
+        self.end_preview();
         self.input_mode = InputMode::Normal;
         self.edit_state = None;
-        self.status_message = Some("Edit cancelled".to_string());
+        self.set_status("Edit cancelled");
+    }
+    
+    /// Start or stop applying each highlighted display mode as the selection moves
+    fn toggle_preview(&mut self) {
+        let Some(edit_state) = &mut self.edit_state else {
+            return;
+        };
+        if edit_state.preview.is_some() {
+            self.end_preview();
+            self.set_status("Preview stopped - display mode restored");
+            return;
+        }
+        
+        match edit_state.editor.capture_display_mode() {
+            Ok(Some(original)) => {
+                edit_state.preview = Some(DisplayPreview {
+                    original,
+                    deadline: Instant::now() + PREVIEW_DURATION,
+                    applying: false,
+                });
+                self.preview_selection();
+            }
+            Ok(None) => {}
+            Err(e) => self.set_error(format!("Preview failed: {}", e)),
+        }
+    }
+    
+    /// Have the worker apply the highlighted mode while previewing, unless it is still applying
+    /// the last one, in which case `on_value_previewed` moves on to this one
+    fn preview_selection(&mut self) {
+        let Some(edit_state) = self.edit_state.as_mut() else {
+            return;
+        };
+        let Some(preview) = edit_state.preview.as_mut().filter(|preview| !preview.applying) else {
+            return;
+        };
+        let Some(value) = edit_state.options.get(edit_state.selected_option_index).map(|option| option.value.clone()) else {
+            return;
+        };
+        preview.applying = true;
+        self.worker.preview_value(self.editor_request, edit_state.editor.clone(), value);
+    }
+    
+    /// Show the mode just applied as the pending one and restart the countdown
+    fn on_value_previewed(&mut self, request: u64, value: SettingValue, result: Result<(), String>) {
+        if request != self.editor_request {
+            return;
+        }
+        // The preview may have ended while the mode was being applied
+        let Some(edit_state) = self.edit_state.as_mut().filter(|edit_state| edit_state.preview.is_some()) else {
+            return;
+        };
+        if let Err(e) = result {
+            self.end_preview();
+            self.set_error(format!("Preview failed: {}", e));
+            return;
+        }
+        
+        let selected = edit_state.options.get(edit_state.selected_option_index).map(|option| &option.value);
+        // Once saving, the save follows this mode and takes over from the preview
+        let moved_on = selected != Some(&value) && !edit_state.saving;
+        edit_state.pending_value = Some(value);
+        if let Some(preview) = &mut edit_state.preview {
+            preview.applying = false;
+            preview.deadline = Instant::now() + PREVIEW_DURATION;
+        }
+        if moved_on {
+            self.preview_selection();
+        }
     }
+    
+    /// Have the worker put back the display mode from before previewing, if a preview is running
+    fn end_preview(&mut self) {
+        if let Some(preview) = self.edit_state.as_mut().and_then(|edit_state| edit_state.preview.take()) {
+            self.worker.restore_display_mode(preview.original);
+        }
+    }
+    
+    fn on_preview_restored(&mut self, result: Result<(), String>) {
+        if let Err(e) = result {
+            self.set_error(format!("Error: failed to restore display mode: {}", e));
+        }
+    }
+}
+
+/// Draw the whole screen, with whichever overlay the current mode shows on top
+fn draw<B: Backend>(terminal: &mut Terminal<B>, app: &App) -> Result<()> {
+    terminal.draw(|f| {
+        if app.input_mode == InputMode::Edit || app.input_mode == InputMode::Confirm {
+            ui::draw(f, app);
+            // Draw edit overlay
+            let area = centered_rect(80, 80, f.size());
+            if let Some(edit_state) = &app.edit_state {
+                edit_ui::draw_edit_panel(f, area, &app.theme, edit_state, app.screen_reader);
+                if let Some(confirmation) = &edit_state.confirmation {
+                    let dialog_area = centered_rect(50, 30, f.size());
+                    edit_ui::draw_confirm_dialog(f, dialog_area, &app.theme, edit_state, confirmation);
+                }
+            } else if let Some(record) = &app.undo_confirmation {
+                let dialog_area = centered_rect(50, 30, f.size());
+                edit_ui::draw_undo_confirm_dialog(f, dialog_area, &app.theme, record);
+            }
+        } else if let Some(batch) = app.batch.as_ref().filter(|_| app.input_mode == InputMode::Batch) {
+            ui::draw(f, app);
+            let area = centered_rect(70, 80, f.size());
+            ui::draw_batch(f, area, &app.theme, &app.keybindings, batch);
+        } else if app.input_mode == InputMode::Help {
+            ui::draw(f, app);
+            let area = centered_rect(60, 80, f.size());
+            ui::draw_help(f, area, &app.keybindings, &app.theme);
+        } else {
+            ui::draw(f, app);
+        }
+    })?;
+    Ok(())
 }
 
 pub fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut app: App,
     event_handler: EventHandler,
-) -> Result<()> {
+) -> Result<AppExit> {
+    let mut redraw = true;
     loop {
-        terminal.draw(|f| {
-            if app.input_mode == InputMode::Edit {
-                ui::draw(f, &app);
-                // Draw edit overlay
-                let area = centered_rect(80, 80, f.size());
-                if let Some(edit_state) = &app.edit_state {
-                    edit_ui::draw_edit_panel(f, area, edit_state);
-                }
-            } else {
-                ui::draw(f, &app);
+        app.refresh_details();
+        app.refresh_item_values();
+        if redraw {
+            draw(terminal, &app)?;
+        }
+        
+        // Idle ticks only expire status messages, so they can be slow
+        event_handler.set_tick_rate(if app.is_animating() { ANIMATION_TICK_RATE } else { app.tick_rate });
+        let event = event_handler.next()?;
+        // Anything but a tick can change what is shown
+        redraw = !matches!(event, Event::Tick);
+        match event {
+            Event::Tick => {
+                redraw = app.on_tick();
             }
-        })?;
-
-        match event_handler.next()? {
-            Event::Tick => {}
             Event::Key(key) => {
                 match app.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('q') => {
-                            app.should_quit = true;
+                    InputMode::Normal => match app.keybindings.action_for(&key, NORMAL_ACTIONS) {
+                        Some(Action::Quit) => {
+                            app.request_quit();
                         }
-                        KeyCode::Char('/') => {
+                        Some(Action::Search) => {
                             app.input_mode = InputMode::Search;
                             app.search_query.clear();
+                            app.history_index = None;
+                        }
+                        Some(Action::Help) => {
+                            app.input_mode = InputMode::Help;
+                        }
+                        Some(Action::CycleTheme) => {
+                            app.cycle_theme();
+                        }
+                        Some(Action::ToggleDetails) => {
+                            app.show_details = !app.show_details;
+                        }
+                        Some(Action::Export) => {
+                            app.export_snapshot();
+                        }
+                        Some(Action::Import) => {
+                            app.start_import();
+                        }
+                        Some(Action::Profiles) => {
+                            app.start_profiles();
+                        }
+                        Some(Action::Rename) => {
+                            app.start_rename();
                         }
-                        KeyCode::Tab => {
+                        Some(Action::RelaunchElevated) => {
+                            if app.elevated {
+                                app.set_status("Already running as administrator");
+                            } else {
+                                app.relaunch_elevated = true;
+                                app.should_quit = true;
+                            }
+                        }
+                        Some(Action::SwitchFocus) => {
                             app.focus_area = match app.focus_area {
                                 FocusArea::Categories => FocusArea::Items,
                                 FocusArea::Items => FocusArea::Categories,
                             };
                         }
-                        KeyCode::Down => match app.focus_area {
-                            FocusArea::Categories => {
-                                if app.category_index < app.categories.len() - 1 {
-                                    app.category_index += 1;
-                                    app.filter_items();
-                                }
-                            }
-                            FocusArea::Items => {
-                                if app.item_index < app.filtered_items.len() - 1 {
-                                    app.item_index += 1;
-                                }
-                            }
-                        },
-                        KeyCode::Up => match app.focus_area {
-                            FocusArea::Categories => {
-                                if app.category_index > 0 {
-                                    app.category_index -= 1;
-                                    app.filter_items();
-                                }
-                            }
-                            FocusArea::Items => {
-                                if app.item_index > 0 {
-                                    app.item_index -= 1;
-                                }
-                            }
-                        },
-                        KeyCode::Enter => {
+                        Some(Action::MoveDown) => {
+                            app.move_down(app.focus_area);
+                        }
+                        Some(Action::MoveUp) => {
+                            app.move_up(app.focus_area);
+                        }
+                        Some(Action::JumpToTop) => {
+                            app.jump_to_top(app.focus_area);
+                        }
+                        Some(Action::JumpToBottom) => {
+                            app.jump_to_bottom(app.focus_area);
+                        }
+                        Some(Action::PageUp) => {
+                            app.move_page(app.focus_area, false, terminal.size()?);
+                        }
+                        Some(Action::PageDown) => {
+                            app.move_page(app.focus_area, true, terminal.size()?);
+                        }
+                        Some(Action::WidenCategories) => {
+                            app.resize_categories(true);
+                        }
+                        Some(Action::NarrowCategories) => {
+                            app.resize_categories(false);
+                        }
+                        Some(Action::ToggleDensity) => {
+                            app.toggle_density();
+                        }
+                        Some(Action::Copy) => {
+                            app.copy_selected();
+                        }
+                        Some(Action::Cancel) => {
+                            app.clear_search();
+                        }
+                        Some(Action::Refresh) => {
+                            // Values changed outside TMWT, e.g. from the system tray, are read again
+                            app.invalidate_values();
+                            app.refresh_editor_caches();
+                            app.set_status("Refreshed - reading current values again");
+                        }
+                        Some(Action::ToggleStaging) => {
+                            app.toggle_staging();
+                        }
+                        Some(Action::ApplyStaged) => {
+                            app.apply_staged();
+                        }
+                        Some(Action::Undo) => {
+                            app.undo_last_change();
+                        }
+                        Some(Action::Favorite) if app.focus_area == FocusArea::Items => {
+                            app.toggle_favorite();
+                        }
+                        Some(Action::FocusCategories) => {
+                            app.focus_area = FocusArea::Categories;
+                        }
+                        Some(Action::FocusItems) => {
+                            app.focus_area = FocusArea::Items;
+                        }
+                        Some(Action::Open | Action::Edit)
+                            if app.focus_area == FocusArea::Items && app.filtered_items.is_empty() =>
+                        {
+                            // An empty search result or category leaves nothing under the cursor
+                            app.set_status(app.empty_list_message());
+                        }
+                        Some(Action::Open) => {
                             if app.focus_area == FocusArea::Items {
                                 if let Some(item) = app.filtered_items.get(app.item_index).cloned() {
                                     if item.can_edit_inline {
                                         // Try to enter edit mode
                                         if let Err(e) = app.enter_edit_mode(&item) {
-                                            app.status_message = Some(format!("Error: {}", e));
+                                            app.set_error(format!("Error: {}", e));
                                         }
                                     } else {
                                         // Fall back to launching the settings panel
-                                        if let Err(e) = launcher::launch_setting(&item) {
-                                            app.status_message = Some(format!("Error: {}", e));
+                                        match launcher::launch_setting(&item) {
+                                            Ok(LaunchOutcome::Launched) => {}
+                                            Ok(LaunchOutcome::ElevationCancelled) => {
+                                                app.set_status(format!("Elevation cancelled, {} was not opened", item.name));
+                                            }
+                                            // The alternate form includes the item and command added as context
+                                            Err(e) => app.set_error(format!("Error: {:#}", e)),
                                         }
                                     }
                                 }
                             }
                         }
-                        KeyCode::Char('e') => {
+                        Some(Action::Edit) => {
                             // Quick edit shortcut
                             if app.focus_area == FocusArea::Items {
                                 if let Some(item) = app.filtered_items.get(app.item_index).cloned() {
                                     if item.can_edit_inline {
                                         if let Err(e) = app.enter_edit_mode(&item) {
-                                            app.status_message = Some(format!("Error: {}", e));
+                                            app.set_error(format!("Error: {}", e));
                                         }
                                     } else {
-                                        app.status_message = Some("This setting cannot be edited inline".to_string());
+                                        app.set_error("This setting cannot be edited inline");
                                     }
                                 }
                             }
                         }
+                        // Unbound digits jump to the category numbered in the list
+                        None => {
+                            if let KeyCode::Char(digit @ '1'..='9') = key.code {
+                                app.jump_to_category(digit as usize - '1' as usize);
+                            }
+                        }
                         _ => {}
                     },
-                    InputMode::Search => match key.code {
-                        KeyCode::Enter => {
+                    InputMode::Search => match app.keybindings.action_for(&key, SEARCH_ACTIONS) {
+                        Some(Action::Accept) => {
+                            app.remember_search();
                             app.input_mode = InputMode::Normal;
                         }
-                        KeyCode::Esc => {
+                        Some(Action::Cancel) => {
                             app.input_mode = InputMode::Normal;
                             app.search_query.clear();
                             app.filter_items();
                         }
-                        KeyCode::Char(c) => {
-                            app.search_query.push(c);
-                            app.filter_items();
+                        _ => match key.code {
+                            KeyCode::Char(c) => {
+                                app.search_query.push(c);
+                                app.history_index = None;
+                                app.filter_items();
+                            }
+                            KeyCode::Backspace => {
+                                app.search_query.pop();
+                                app.history_index = None;
+                                app.filter_items();
+                            }
+                            // Fixed arrows, since the rebindable move keys include letters
+                            KeyCode::Up => app.recall_search(true),
+                            KeyCode::Down => app.recall_search(false),
+                            _ => {}
+                        },
+                    },
+                    InputMode::Batch => {
+                        app.handle_batch_key(key);
+                    }
+                    InputMode::Help => {
+                        if app.keybindings.action_for(&key, HELP_ACTIONS).is_some() {
+                            app.input_mode = InputMode::Normal;
                         }
-                        KeyCode::Backspace => {
-                            app.search_query.pop();
-                            app.filter_items();
+                    }
+                    InputMode::Confirm => {
+                        let keeping_changes = matches!(
+                            app.edit_state.as_ref().and_then(|edit_state| edit_state.confirmation.as_ref()),
+                            Some(Confirmation::KeepChanges { .. })
+                        );
+                        if app.keybindings.action_for(&key, &[Action::Cancel]).is_some() {
+                            app.confirm_no();
+                        } else {
+                            match key.code {
+                                KeyCode::Char('n') | KeyCode::Char('N') => {
+                                    app.confirm_no();
+                                }
+                                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                    app.confirm_yes();
+                                }
+                                // Any key proves the screen is readable, so keep the change
+                                _ if keeping_changes => {
+                                    app.confirm_yes();
+                                }
+                                _ => {}
+                            }
                         }
-                        _ => {}
-                    },
+                    }
                     InputMode::Edit => {
+                        let pending_before = app.edit_state.as_ref().and_then(|edit_state| edit_state.pending_value.clone());
                         if let Some(edit_state) = &mut app.edit_state {
-                            match key.code {
-                                KeyCode::Esc => {
+                            if edit_state.saving {
+                                // The value is being applied, and keys could close the panel under it
+                            } else if edit_state.loading {
+                                // Nothing to edit until the worker delivers the options
+                                if app.keybindings.action_for(&key, &[Action::Cancel]).is_some() {
                                     app.cancel_edit();
                                 }
-                                KeyCode::Char('s') | KeyCode::Char('S') => {
-                                    if let Err(e) = app.save_edit() {
-                                        app.status_message = Some(format!("Save failed: {}", e));
+                            } else if let EditorType::TextInput { multiline } = edit_state.editor_type {
+                                // Plain characters are text, so saving uses its own binding here
+                                match app.keybindings.action_for(&key, TEXT_EDIT_ACTIONS) {
+                                    Some(Action::Cancel) => {
+                                        app.cancel_edit();
                                     }
-                                }
-                                KeyCode::Enter | KeyCode::Char(' ') => {
-                                    match &edit_state.editor_type {
-                                        EditorType::Toggle => {
-                                            // Toggle the value
-                                            let new_val = match &edit_state.pending_value {
-                                                Some(SettingValue::Bool(b)) => SettingValue::Bool(!b),
-                                                _ => SettingValue::Bool(true),
-                                            };
-                                            edit_state.pending_value = Some(new_val);
+                                    Some(Action::SaveText) => {
+                                        app.try_save_edit();
+                                    }
+                                    _ => match key.code {
+                                        KeyCode::Char(c) => {
+                                            edit_state.insert_char(c);
                                         }
-                                        EditorType::Dropdown | EditorType::ResolutionPicker => {
-                                            // Select current option
-                                            if let Some(option) = edit_state.options.get(edit_state.selected_option_index) {
-                                                edit_state.pending_value = Some(option.value.clone());
-                                            }
+                                        KeyCode::Enter if multiline => {
+                                            edit_state.insert_char('\n');
+                                        }
+                                        KeyCode::Backspace => {
+                                            edit_state.delete_char_before_cursor();
+                                        }
+                                        KeyCode::Tab => {
+                                            edit_state.complete_suggestion();
+                                        }
+                                        KeyCode::Left => {
+                                            edit_state.move_cursor_left();
+                                        }
+                                        KeyCode::Right => {
+                                            edit_state.move_cursor_right();
                                         }
                                         _ => {}
-                                    }
+                                    },
                                 }
-                                KeyCode::Up => {
-                                    if edit_state.selected_option_index > 0 {
-                                        edit_state.selected_option_index -= 1;
+                            } else {
+                                // Matches the overlay drawn above, so the picker knows how many rows it shows
+                                let picker_height = edit_ui::editor_area(centered_rect(80, 80, terminal.size()?)).height;
+                                match app.keybindings.action_for(&key, EDIT_ACTIONS) {
+                                    Some(Action::Cancel) => {
+                                        app.cancel_edit();
                                     }
-                                }
-                                KeyCode::Down => {
-                                    if edit_state.selected_option_index < edit_state.options.len() - 1 {
-                                        edit_state.selected_option_index += 1;
+                                    Some(Action::Save) => {
+                                        app.try_save_edit();
                                     }
-                                }
-                                KeyCode::Left => {
-                                    if let EditorType::Slider { min, max: _, step } = &edit_state.editor_type {
-                                        if let Some(SettingValue::Float(val)) = &mut edit_state.pending_value {
-                                            let adjustment = if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                                step * 0.1
-                                            } else {
-                                                *step
-                                            };
-                                            *val = (*val - adjustment).max(*min);
+                                    Some(Action::Quit) => {
+                                        app.request_quit();
+                                    }
+                                    Some(Action::Select) => {
+                                        match &edit_state.editor_type {
+                                            EditorType::Toggle => {
+                                                // Toggle the value
+                                                let new_val = match &edit_state.pending_value {
+                                                    Some(SettingValue::Bool(b)) => SettingValue::Bool(!b),
+                                                    _ => SettingValue::Bool(true),
+                                                };
+                                                edit_state.pending_value = Some(new_val);
+                                            }
+                                            EditorType::MultiSelect => {
+                                                // Switch just the highlighted entry
+                                                let id = match edit_state.options.get(edit_state.selected_option_index) {
+                                                    Some(SettingOption { value: SettingValue::Selection(id), .. }) => Some(id.clone()),
+                                                    _ => None,
+                                                };
+                                                if let (Some(id), Some(SettingValue::Flags(flags))) = (id, &mut edit_state.pending_value) {
+                                                    let enabled = flags.entry(id).or_insert(false);
+                                                    *enabled = !*enabled;
+                                                }
+                                            }
+                                            EditorType::Dropdown | EditorType::ResolutionPicker => {
+                                                if let Some(target_editor) = edit_state.choose_highlighted() {
+                                                    // Move on to editing the chosen target
+                                                    let item_name = edit_state.item_name.clone();
+                                                    app.open_editor(item_name, target_editor);
+                                                }
+                                            }
+                                            _ => {}
                                         }
                                     }
-                                }
-                                KeyCode::Right => {
-                                    if let EditorType::Slider { min: _, max, step } = &edit_state.editor_type {
-                                        if let Some(SettingValue::Float(val)) = &mut edit_state.pending_value {
-                                            let adjustment = if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                                step * 0.1
-                                            } else {
-                                                *step
-                                            };
-                                            *val = (*val + adjustment).min(*max);
+                                    Some(Action::MoveUp) => {
+                                        if let EditorType::NumberInput { .. } = edit_state.editor_type {
+                                            edit_state.adjust_number(1);
+                                        } else if edit_state.step_option(false, app.wrap_navigation, picker_height) {
+                                            app.preview_selection();
+                                        }
+                                    }
+                                    Some(Action::MoveDown) => {
+                                        if let EditorType::NumberInput { .. } = edit_state.editor_type {
+                                            edit_state.adjust_number(-1);
+                                        } else if edit_state.step_option(true, app.wrap_navigation, picker_height) {
+                                            app.preview_selection();
                                         }
                                     }
+                                    // Editor-specific keys are not configurable
+                                    _ => match key.code {
+                                        KeyCode::Char('p') | KeyCode::Char('P')
+                                            if matches!(edit_state.editor_type, EditorType::ResolutionPicker) =>
+                                        {
+                                            app.toggle_preview();
+                                        }
+                                        KeyCode::Char(c) if c.is_ascii_digit() => {
+                                            if let Some(digit) = c.to_digit(10) {
+                                                edit_state.push_digit(digit);
+                                            }
+                                        }
+                                        KeyCode::Backspace => {
+                                            edit_state.pop_digit();
+                                        }
+                                        KeyCode::PageUp => {
+                                            edit_state.adjust_number(10);
+                                        }
+                                        KeyCode::PageDown => {
+                                            edit_state.adjust_number(-10);
+                                        }
+                                        KeyCode::Left | KeyCode::Right => {
+                                            let fine = key.modifiers.contains(KeyModifiers::SHIFT);
+                                            edit_state.nudge_slider(key.code == KeyCode::Right, fine);
+                                        }
+                                        _ => {}
+                                    },
                                 }
-                                _ => {}
+                            }
+                        }
+                        // A different value makes the last validation error and check stale
+                        if let Some(edit_state) = &mut app.edit_state {
+                            if edit_state.pending_value != pending_before {
+                                edit_state.error_message = None;
+                                app.check_pending_value();
                             }
                         }
                     }
                 }
             }
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse) => {
+                if app.input_mode == InputMode::Normal {
+                    app.handle_mouse(mouse, terminal.size()?);
+                }
+            }
+            Event::DoubleClick(mouse) => {
+                if app.input_mode == InputMode::Normal {
+                    app.handle_double_click(mouse, terminal.size()?);
+                }
+            }
             Event::Resize(_, _) => {}
+            Event::EditorLoaded(loaded) => {
+                app.on_editor_loaded(loaded);
+            }
+            Event::DetailLoaded { item_name, result } => {
+                app.on_detail_loaded(item_name, result);
+            }
+            Event::BatchApplied(results) => {
+                app.on_batch_applied(results);
+            }
+            Event::ProfileCaptured(result) => match result {
+                Ok((name, count)) => app.set_status(format!("✓ Saved profile '{}' with {} settings", name, count)),
+                Err(e) => app.set_error(format!("Capturing profile failed: {}", e)),
+            },
+            Event::SnapshotExported(result) => match result {
+                Ok(path) => app.set_status(format!("✓ Settings exported to {}", path.display())),
+                Err(e) => app.set_error(format!("Export failed: {}", e)),
+            },
+            Event::ValueCopied(result) => {
+                app.on_value_copied(result);
+            }
+            Event::ValueChecked { request, value, warning } => {
+                app.on_value_checked(request, value, warning);
+            }
+            Event::ValueSaved { request, result } => {
+                app.on_value_saved(request, result);
+            }
+            Event::ChangeUndone { item_name, label, result } => {
+                app.on_change_undone(item_name, label, result);
+            }
+            Event::ValuePreviewed { request, value, result } => {
+                app.on_value_previewed(request, value, result);
+            }
+            Event::PreviewRestored(result) => {
+                app.on_preview_restored(result);
+            }
+            Event::AdaptersListed(result) => {
+                app.on_adapters_listed(result);
+            }
         }
 
         if app.should_quit {
@@ -354,7 +2511,15 @@ pub fn run_app<B: Backend>(
         }
     }
 
-    Ok(())
+    // A display mode being put back or a value being saved is seen through before exiting
+    app.worker.finish_writes();
+    // Nowhere to report a failure once the UI is gone, and it only costs the cursor position
+    let _ = app.save_state();
+    Ok(if app.relaunch_elevated {
+        AppExit::RelaunchElevated
+    } else {
+        AppExit::Quit
+    })
 }
 
 /// Helper function to create centered rect
@@ -376,4 +2541,177 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: ratatui::layout::Rect) -> ra
             ratatui::layout::Constraint::Percentage((100 - percent_x) / 2),
         ])
         .split(popup_layout[1])[1]
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tmwt::display_editor::BrightnessEditor;
+    
+    /// A dropdown whose setting reports nothing to choose from
+    #[derive(Debug, Clone)]
+    struct NoOptionsEditor;
+    
+    impl SettingEditor for NoOptionsEditor {
+        fn clone_box(&self) -> Box<dyn SettingEditor> {
+            Box::new(self.clone())
+        }
+        
+        fn get_current_value(&self) -> Result<SettingValue> {
+            Ok(SettingValue::Selection("current".to_string()))
+        }
+        
+        fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+            Ok(vec![])
+        }
+        
+        fn set_value(&self, _value: SettingValue) -> Result<()> {
+            Ok(())
+        }
+        
+        fn validate_value(&self, _value: &SettingValue) -> Result<bool> {
+            Ok(false)
+        }
+        
+        fn get_editor_type(&self) -> EditorType {
+            EditorType::Dropdown
+        }
+        
+        fn requires_admin(&self) -> bool {
+            false
+        }
+    }
+    
+    /// A slider editor's edit session, already showing `value`
+    fn slider(editor: Box<dyn SettingEditor>, value: f64) -> EditState {
+        let mut edit_state = EditState::loading("Slider".to_string(), editor, false);
+        edit_state.pending_value = Some(SettingValue::Float(value));
+        edit_state.loading = false;
+        edit_state
+    }
+    
+    #[test]
+    fn fine_slider_steps_are_a_tenth_of_a_step_by_default() {
+        let mut edit_state = slider(Box::new(BrightnessEditor::new()), 50.0);
+        edit_state.nudge_slider(true, true);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Float(50.5)));
+        edit_state.nudge_slider(false, false);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Float(45.5)));
+    }
+    
+    #[test]
+    fn fine_volume_steps_move_by_one_percent() {
+        let mut edit_state = slider(Box::new(VolumeEditor::new()), 50.0);
+        edit_state.nudge_slider(true, true);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Float(51.0)));
+    }
+    
+    /// A number input bounded to 200-900, already showing `value`
+    fn number_input(value: i64) -> EditState {
+        let mut edit_state = EditState::loading("Number".to_string(), Box::new(NoOptionsEditor), false);
+        edit_state.editor_type = EditorType::NumberInput { min: Some(200), max: Some(900) };
+        edit_state.set_number(value);
+        edit_state.loading = false;
+        edit_state
+    }
+    
+    #[test]
+    fn typed_numbers_pass_through_out_of_range_values() {
+        let mut edit_state = number_input(500);
+        for _ in 0..3 {
+            edit_state.pop_digit();
+        }
+        assert_eq!(edit_state.pending_value, None);
+        
+        for digit in [3, 5, 0] {
+            edit_state.push_digit(digit);
+        }
+        edit_state.clamp_number_entry();
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Integer(350)));
+    }
+    
+    #[test]
+    fn typed_numbers_are_pinned_to_the_bounds_on_save() {
+        let mut edit_state = number_input(500);
+        edit_state.push_digit(0);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Integer(5000)));
+        edit_state.clamp_number_entry();
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Integer(900)));
+        assert_eq!(edit_state.number_text, "900");
+        assert_eq!(edit_state.pinned_bound(), Some(900));
+    }
+    
+    #[test]
+    fn only_pinned_bounds_are_reported() {
+        let mut edit_state = number_input(210);
+        edit_state.adjust_number(-10);
+        assert_eq!(edit_state.pinned_bound(), None);
+        
+        edit_state.adjust_number(-10);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Integer(200)));
+        assert_eq!(edit_state.pinned_bound(), Some(200));
+        
+        edit_state.pop_digit();
+        assert_eq!(edit_state.pinned_bound(), Some(200));
+        edit_state.push_digit(5);
+        assert_eq!(edit_state.pinned_bound(), None);
+    }
+    
+    #[test]
+    fn digits_extend_negative_numbers() {
+        let mut edit_state = number_input(-5);
+        edit_state.editor_type = EditorType::NumberInput { min: None, max: None };
+        edit_state.push_digit(7);
+        assert_eq!(edit_state.pending_value, Some(SettingValue::Integer(-57)));
+    }
+    
+    #[test]
+    fn empty_option_lists_ignore_navigation_and_selection() {
+        let editor = NoOptionsEditor;
+        let current = editor.get_current_value().unwrap();
+        let mut edit_state = EditState::loading("Empty".to_string(), editor.clone_box(), false);
+        edit_state.options = editor.get_available_options().unwrap();
+        edit_state.pending_value = Some(current.clone());
+        edit_state.loading = false;
+        
+        for wrap in [true, false] {
+            assert!(!edit_state.step_option(false, wrap, 10));
+            assert!(!edit_state.step_option(true, wrap, 10));
+        }
+        assert!(edit_state.choose_highlighted().is_none());
+        
+        assert_eq!(edit_state.selected_option_index, 0);
+        assert_eq!(edit_state.pending_value, Some(current));
+    }
+
+    #[test]
+    fn step_index_stays_put_in_an_empty_list() {
+        for forward in [true, false] {
+            for wrap in [true, false] {
+                assert_eq!(step_index(0, 0, forward, wrap), None);
+            }
+        }
+    }
+
+    #[test]
+    fn step_index_stays_put_in_a_single_entry_list() {
+        for forward in [true, false] {
+            for wrap in [true, false] {
+                assert_eq!(step_index(0, 1, forward, wrap), None);
+            }
+        }
+    }
+
+    #[test]
+    fn step_index_moves_within_the_list() {
+        assert_eq!(step_index(0, 3, true, false), Some(1));
+        assert_eq!(step_index(2, 3, false, false), Some(1));
+    }
+
+    #[test]
+    fn step_index_wraps_only_when_asked() {
+        assert_eq!(step_index(2, 3, true, false), None);
+        assert_eq!(step_index(2, 3, true, true), Some(0));
+        assert_eq!(step_index(0, 3, false, false), None);
+        assert_eq!(step_index(0, 3, false, true), Some(2));
+    }
+}
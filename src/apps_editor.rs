@@ -0,0 +1,229 @@
+use crate::registry::{list_value_names, read_binary, read_string, write_binary};
+use crate::settings_editor::{EditorType, SettingEditor, SettingOption, SettingValue};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use windows::Win32::System::Registry::{HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+const APPROVED_RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\Run";
+const APPROVED_FOLDER_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\StartupFolder";
+
+/// Where a startup entry is registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StartupLocation {
+    CurrentUserRun,
+    AllUsersRun,
+    StartupFolder,
+}
+
+impl StartupLocation {
+    const ALL: [StartupLocation; 3] = [
+        StartupLocation::CurrentUserRun,
+        StartupLocation::AllUsersRun,
+        StartupLocation::StartupFolder,
+    ];
+    
+    /// Prefix of the entry ids, keeping same-named entries in different places apart
+    fn id_prefix(&self) -> &'static str {
+        match self {
+            StartupLocation::CurrentUserRun => "HKCU",
+            StartupLocation::AllUsersRun => "HKLM",
+            StartupLocation::StartupFolder => "Folder",
+        }
+    }
+    
+    fn description(&self) -> &'static str {
+        match self {
+            StartupLocation::CurrentUserRun => "current user",
+            StartupLocation::AllUsersRun => "all users",
+            StartupLocation::StartupFolder => "Startup folder",
+        }
+    }
+    
+    /// Key holding the enabled flags Task Manager keeps for this location
+    fn approved_key(&self) -> (HKEY, &'static str) {
+        match self {
+            StartupLocation::CurrentUserRun => (HKEY_CURRENT_USER, APPROVED_RUN_KEY),
+            StartupLocation::AllUsersRun => (HKEY_LOCAL_MACHINE, APPROVED_RUN_KEY),
+            StartupLocation::StartupFolder => (HKEY_CURRENT_USER, APPROVED_FOLDER_KEY),
+        }
+    }
+}
+
+/// A program launched at sign-in
+#[derive(Debug, Clone)]
+struct StartupEntry {
+    location: StartupLocation,
+    /// Registry value name, or file name in the Startup folder
+    name: String,
+    /// Command line, or the shortcut's path for Startup folder entries
+    command: String,
+    enabled: bool,
+}
+
+impl StartupEntry {
+    fn id(&self) -> String {
+        format!("{}:{}", self.location.id_prefix(), self.name)
+    }
+}
+
+/// Startup apps from the Run keys and the Startup folder, switched on and off
+/// like Task Manager does: the entry stays put and a flag under `StartupApproved`
+/// records whether it runs, so disabled entries can be enabled again
+#[derive(Debug, Clone, Default)]
+pub struct StartupAppsEditor;
+
+impl StartupAppsEditor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    fn get_entries(&self) -> Result<Vec<StartupEntry>> {
+        let mut entries = Vec::new();
+        for location in StartupLocation::ALL {
+            let names_and_commands = match location {
+                StartupLocation::CurrentUserRun | StartupLocation::AllUsersRun => {
+                    let root = if location == StartupLocation::CurrentUserRun {
+                        HKEY_CURRENT_USER
+                    } else {
+                        HKEY_LOCAL_MACHINE
+                    };
+                    let mut values = Vec::new();
+                    for name in list_value_names(root, RUN_KEY)? {
+                        // Only string values are commands; anything else isn't launched
+                        if let Ok(Some(command)) = read_string(root, RUN_KEY, &name) {
+                            values.push((name, command));
+                        }
+                    }
+                    values
+                }
+                StartupLocation::StartupFolder => startup_folder_files(),
+            };
+            
+            let (approved_root, approved_key) = location.approved_key();
+            for (name, command) in names_and_commands {
+                let enabled = read_binary(approved_root, approved_key, &name)?
+                    .is_none_or(|flag| is_approved(&flag));
+                entries.push(StartupEntry {
+                    location,
+                    name,
+                    command,
+                    enabled,
+                });
+            }
+        }
+        Ok(entries)
+    }
+    
+    fn set_enabled(&self, entry: &StartupEntry, enabled: bool) -> Result<()> {
+        let (root, key) = entry.location.approved_key();
+        write_binary(root, key, &entry.name, &approved_flag(enabled))
+            .with_context(|| format!("Failed to change startup entry '{}'", entry.name))
+    }
+}
+
+/// Files in the current user's Startup folder as (file name, path)
+fn startup_folder_files() -> Vec<(String, String)> {
+    let Some(dir) = dirs::data_dir().map(|dir| dir.join(r"Microsoft\Windows\Start Menu\Programs\Startup")) else {
+        return vec![];
+    };
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        // Explorer keeps a hidden desktop.ini in the folder
+        .filter(|path| path.is_file() && path.file_name().is_some_and(|name| name != "desktop.ini"))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            Some((name, path.display().to_string()))
+        })
+        .collect()
+}
+
+/// `StartupApproved` flags start with an even byte when enabled and an odd one when disabled
+fn is_approved(flag: &[u8]) -> bool {
+    flag.first().is_none_or(|state| state % 2 == 0)
+}
+
+/// The 12-byte flag Task Manager writes: the state, three zero bytes and, when
+/// disabling, the time it was disabled as a `FILETIME`
+fn approved_flag(enabled: bool) -> [u8; 12] {
+    let mut flag = [0u8; 12];
+    if enabled {
+        flag[0] = 0x02;
+    } else {
+        flag[0] = 0x03;
+        // FILETIME counts 100ns intervals since 1601
+        let since_unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let filetime = (since_unix.as_secs() + 11_644_473_600) * 10_000_000 + u64::from(since_unix.subsec_nanos() / 100);
+        flag[4..].copy_from_slice(&filetime.to_le_bytes());
+    }
+    flag
+}
+
+impl SettingEditor for StartupAppsEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let flags = self
+            .get_entries()?
+            .into_iter()
+            .map(|entry| (entry.id(), entry.enabled))
+            .collect();
+        Ok(SettingValue::Flags(flags))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(self
+            .get_entries()?
+            .into_iter()
+            .map(|entry| SettingOption {
+                label: entry.name.clone(),
+                value: SettingValue::Selection(entry.id()),
+                description: Some(format!("{} ({})", entry.command, entry.location.description())),
+            })
+            .collect())
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Flags(flags) = value {
+            let entries: BTreeMap<String, StartupEntry> = self
+                .get_entries()?
+                .into_iter()
+                .map(|entry| (entry.id(), entry))
+                .collect();
+            
+            for (id, enabled) in flags {
+                let entry = entries
+                    .get(&id)
+                    .with_context(|| format!("Startup entry '{}' no longer exists", id))?;
+                // Only touch what changed, so all-users entries don't need elevation unless edited
+                if entry.enabled != enabled {
+                    self.set_enabled(entry, enabled)?;
+                }
+            }
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for startup apps")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        Ok(matches!(value, SettingValue::Flags(_)))
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::MultiSelect
+    }
+    
+    fn requires_admin(&self) -> bool {
+        // Only all-users entries need it, and those fail with a clear error on their own
+        false
+    }
+}
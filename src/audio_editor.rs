@@ -0,0 +1,246 @@
+use crate::settings_editor::{EditorType, SettingEditor, SettingOption, SettingValue};
+use anyhow::{Context, Result};
+use windows::{
+    core::{GUID, PCWSTR},
+    Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
+    Win32::Media::Audio::{
+        eCapture, eCommunications, eConsole, eMultimedia, eRender, EDataFlow, ERole, IMMDevice, IMMDeviceEnumerator,
+        MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+    },
+    Win32::System::Com::{CoCreateInstance, CoTaskMemFree, CLSCTX_ALL, STGM_READ},
+};
+use crate::settings_editor::{init_com, to_wide};
+
+/// CLSID of the undocumented policy config object behind the Sound control panel's
+/// "Set Default" button, the only way to change the default endpoint
+const CLSID_POLICY_CONFIG_CLIENT: GUID = GUID::from_u128(0x870af99c_171d_4f9e_af0d_e63df40c2bc9);
+
+/// Undocumented interface implemented by `CLSID_POLICY_CONFIG_CLIENT`, kept in its own
+/// module because the generated vtable has to use the COM method names
+#[allow(non_snake_case)]
+mod policy_config {
+    use windows::core::{interface, IUnknown, IUnknown_Vtbl, HRESULT, PCWSTR};
+    use windows::Win32::Media::Audio::ERole;
+    use std::ffi::c_void;
+
+    /// Only `SetDefaultEndpoint` is called; the methods before it keep the vtable in order
+    #[interface("f8679f50-850a-41cf-9c72-430f290290c8")]
+    pub unsafe trait IPolicyConfig: IUnknown {
+        pub fn GetMixFormat(&self, device_id: PCWSTR, format: *mut *mut c_void) -> HRESULT;
+        pub fn GetDeviceFormat(&self, device_id: PCWSTR, default: i32, format: *mut *mut c_void) -> HRESULT;
+        pub fn ResetDeviceFormat(&self, device_id: PCWSTR) -> HRESULT;
+        pub fn SetDeviceFormat(&self, device_id: PCWSTR, endpoint_format: *mut c_void, mix_format: *mut c_void) -> HRESULT;
+        pub fn GetProcessingPeriod(&self, device_id: PCWSTR, default: i32, default_period: *mut i64, min_period: *mut i64) -> HRESULT;
+        pub fn SetProcessingPeriod(&self, device_id: PCWSTR, period: *mut i64) -> HRESULT;
+        pub fn GetShareMode(&self, device_id: PCWSTR, mode: *mut c_void) -> HRESULT;
+        pub fn SetShareMode(&self, device_id: PCWSTR, mode: *mut c_void) -> HRESULT;
+        pub fn GetPropertyValue(&self, device_id: PCWSTR, key: *const c_void, value: *mut c_void) -> HRESULT;
+        pub fn SetPropertyValue(&self, device_id: PCWSTR, key: *const c_void, value: *mut c_void) -> HRESULT;
+        pub fn SetDefaultEndpoint(&self, device_id: PCWSTR, role: ERole) -> HRESULT;
+        pub fn SetEndpointVisibility(&self, device_id: PCWSTR, visible: i32) -> HRESULT;
+    }
+}
+use policy_config::IPolicyConfig;
+
+/// An active audio endpoint, identified by its Core Audio endpoint ID
+#[derive(Debug, Clone)]
+pub struct AudioEndpoint {
+    pub id: String,
+    pub name: String,
+}
+
+/// Core Audio's device enumerator, the way into every endpoint
+pub(crate) fn device_enumerator() -> Result<IMMDeviceEnumerator> {
+    init_com();
+    unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL) }.context("Failed to create audio device enumerator")
+}
+
+/// Endpoint ID of `device`, which COM hands back as a string we have to free
+fn device_id(device: &IMMDevice) -> Result<String> {
+    unsafe {
+        let id = device.GetId().context("Failed to read audio device ID")?;
+        let result = id.to_string().context("Audio device ID is not valid UTF-16");
+        CoTaskMemFree(Some(id.0 as *const _));
+        result
+    }
+}
+
+fn friendly_name(device: &IMMDevice) -> Result<String> {
+    unsafe {
+        let store = device.OpenPropertyStore(STGM_READ).context("Failed to open audio device properties")?;
+        let name = store
+            .GetValue(&PKEY_Device_FriendlyName)
+            .context("Failed to read audio device name")?;
+        Ok(name.to_string())
+    }
+}
+
+/// Active endpoints that play (`eRender`) or record (`eCapture`) audio
+pub fn audio_endpoints(flow: EDataFlow) -> Result<Vec<AudioEndpoint>> {
+    let enumerator = device_enumerator()?;
+    unsafe {
+        let devices = enumerator
+            .EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)
+            .context("Failed to list audio devices")?;
+        let count = devices.GetCount().context("Failed to count audio devices")?;
+        (0..count)
+            .map(|index| {
+                let device = devices.Item(index).context("Failed to open audio device")?;
+                Ok(AudioEndpoint {
+                    id: device_id(&device)?,
+                    name: friendly_name(&device)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Endpoint ID of the current default device for `flow` in `role`
+pub fn default_endpoint(flow: EDataFlow, role: ERole) -> Result<String> {
+    let enumerator = device_enumerator()?;
+    let device = unsafe { enumerator.GetDefaultAudioEndpoint(flow, role) }.context("No default audio device is set")?;
+    device_id(&device)
+}
+
+/// Make endpoint `id` the default for each of `roles`
+pub fn set_default_endpoint(id: &str, roles: &[ERole]) -> Result<()> {
+    init_com();
+    let policy: IPolicyConfig = unsafe { CoCreateInstance(&CLSID_POLICY_CONFIG_CLIENT, None, CLSCTX_ALL) }
+        .context("Failed to access the audio policy service")?;
+    let wide_id = to_wide(id);
+    for &role in roles {
+        unsafe { policy.SetDefaultEndpoint(PCWSTR(wide_id.as_ptr()), role) }
+            .ok()
+            .context("Failed to change the default audio device")?;
+    }
+    Ok(())
+}
+
+/// Default playback device
+#[derive(Debug, Clone, Default)]
+pub struct AudioDeviceEditor;
+
+impl AudioDeviceEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SettingEditor for AudioDeviceEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        Ok(SettingValue::Selection(default_endpoint(eRender, eConsole)?))
+    }
+
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(audio_endpoints(eRender)?
+            .into_iter()
+            .map(|endpoint| SettingOption {
+                label: endpoint.name,
+                value: SettingValue::Selection(endpoint.id),
+                description: None,
+            })
+            .collect())
+    }
+
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Selection(id) = value {
+            // Switch every role so calls follow the new device too, not just media playback
+            set_default_endpoint(&id, &[eConsole, eMultimedia, eCommunications])
+        } else {
+            anyhow::bail!("Invalid value type for audio device")
+        }
+    }
+
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Selection(id) = value {
+            if !audio_endpoints(eRender)?.iter().any(|endpoint| &endpoint.id == id) {
+                anyhow::bail!("That playback device is no longer connected");
+            }
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Dropdown
+    }
+
+    fn requires_admin(&self) -> bool {
+        false
+    }
+}
+
+/// Default recording device
+#[derive(Debug, Clone, Default)]
+pub struct DefaultInputDeviceEditor;
+
+impl DefaultInputDeviceEditor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn capture_endpoints(&self) -> Result<Vec<AudioEndpoint>> {
+        let endpoints = audio_endpoints(eCapture)?;
+        if endpoints.is_empty() {
+            anyhow::bail!("No microphones or other recording devices are connected");
+        }
+        Ok(endpoints)
+    }
+}
+
+impl SettingEditor for DefaultInputDeviceEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        // Checked first so a machine without microphones gets a clearer error than "no default"
+        self.capture_endpoints()?;
+        Ok(SettingValue::Selection(default_endpoint(eCapture, eConsole)?))
+    }
+
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(self
+            .capture_endpoints()?
+            .into_iter()
+            .map(|endpoint| SettingOption {
+                label: endpoint.name,
+                value: SettingValue::Selection(endpoint.id),
+                description: None,
+            })
+            .collect())
+    }
+
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Selection(id) = value {
+            // Communications covers calling apps, console everything else that records
+            set_default_endpoint(&id, &[eConsole, eCommunications])
+        } else {
+            anyhow::bail!("Invalid value type for microphone")
+        }
+    }
+
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Selection(id) = value {
+            if !self.capture_endpoints()?.iter().any(|endpoint| &endpoint.id == id) {
+                anyhow::bail!("That recording device is no longer connected");
+            }
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Dropdown
+    }
+
+    fn requires_admin(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,265 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::{collections::BTreeMap, process::ExitCode};
+use crate::{
+    launcher,
+    network_editor::list_network_adapters,
+    settings::{network_adapter_items, Category, SettingsItem, SETTINGS_ITEMS},
+    settings_editor::{create_editor, option_label, EditorType, SettingEditor, SettingOption, SettingValue},
+};
+
+/// Run the subcommand named by the command line arguments (without the program name
+/// and `--dry-run`), or `None` when they name none and the TUI should start.
+/// `--json` makes the output JSON instead of text.
+pub fn run(args: &[String]) -> Option<ExitCode> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let args: Vec<&str> = args.iter().map(String::as_str).filter(|arg| *arg != "--json").collect();
+    let result = match args[..] {
+        ["list"] => list(json),
+        ["get", key] => get(key, json),
+        // `apply` is the original name of `set`
+        ["set" | "apply", key, value] => set(key, value, json),
+        ["list", ..] => Err(anyhow!("Usage: tmwt list [--json]")),
+        ["get", ..] => Err(anyhow!("Usage: tmwt get <setting> [--json]")),
+        ["set" | "apply", ..] => Err(anyhow!("Usage: tmwt set <setting> <value> [--json]")),
+        _ => return None,
+    };
+    Some(match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    })
+}
+
+/// An item of the catalog as `list --json` prints it
+#[derive(Serialize)]
+struct ListedItem<'a> {
+    name: &'a str,
+    category: &'a Category,
+    description: Option<&'a str>,
+    requires_admin: bool,
+    can_edit_inline: bool,
+    /// What `get` and `set` take to name the setting
+    editor_key: Option<&'a str>,
+}
+
+/// A setting's value as `get --json` and `set --json` print it
+#[derive(Serialize)]
+struct PrintedValue<'a> {
+    key: &'a str,
+    value: &'a SettingValue,
+    /// The value as the TUI shows it, e.g. a power plan's name rather than its GUID
+    label: String,
+}
+
+/// Print every item of the catalog with its editor key, grouped by category
+fn list(json: bool) -> Result<()> {
+    let mut items: Vec<SettingsItem> = SETTINGS_ITEMS.clone();
+    // Adapter names differ per machine, so their items are only known once discovered
+    if let Ok(adapters) = list_network_adapters() {
+        items.extend(network_adapter_items(&adapters));
+    }
+    let categories = Category::all();
+    items.sort_by_key(|item| categories.iter().position(|category| *category == item.category));
+    
+    if json {
+        let listed: Vec<ListedItem> = items
+            .iter()
+            .map(|item| ListedItem {
+                name: &item.name,
+                category: &item.category,
+                description: item.description.as_deref(),
+                requires_admin: item.requires_admin,
+                can_edit_inline: item.can_edit_inline,
+                editor_key: item.editor_key.as_deref(),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&listed)?);
+        return Ok(());
+    }
+    
+    let mut category = None;
+    for item in &items {
+        if category != Some(&item.category) {
+            category = Some(&item.category);
+            println!("{}", item.category.display_name());
+        }
+        // Items without an editor can only be opened in Windows Settings
+        let key = item.editor_key.as_deref().unwrap_or("-");
+        let admin = if item.requires_admin { " [Admin]" } else { "" };
+        println!("  {:<32} {}{}", key, item.name, admin);
+    }
+    Ok(())
+}
+
+/// Print the current value of the setting with editor key `key`, e.g. `get power_plan`
+fn get(key: &str, json: bool) -> Result<()> {
+    let editor = editor_for(key)?;
+    if !editor.can_read() {
+        bail!("The current value of {} can't be read yet", key);
+    }
+    let value = editor.get_current_value()?;
+    let options = editor.get_available_options().unwrap_or_default();
+    let label = option_label(editor.as_ref(), &options, &value);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&PrintedValue { key, value: &value, label })?);
+    } else {
+        println!("{}", label);
+    }
+    Ok(())
+}
+
+/// Set the setting with editor key `key` to `value`, e.g. `set display_resolution 2560x1440`
+fn set(key: &str, value: &str, json: bool) -> Result<()> {
+    let editor = editor_for(key)?;
+    if !editor.can_write() {
+        bail!("{} can't be changed from TMWT yet", key);
+    }
+    if editor.requires_admin() && !launcher::is_elevated() {
+        bail!("{} needs administrator rights - run tmwt from an elevated terminal", key);
+    }
+    let options = editor.get_available_options()?;
+    let current = if editor.can_read() { Some(editor.get_current_value()?) } else { None };
+    let value = parse_value(editor.as_ref(), &options, current.as_ref(), value)?;
+    if !editor.validate_value(&value)? {
+        bail!("{} is not a valid value for {}", option_label(editor.as_ref(), &options, &value), key);
+    }
+    editor.set_value(value.clone())?;
+    let label = option_label(editor.as_ref(), &options, &value);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&PrintedValue { key, value: &value, label })?);
+    } else {
+        println!("{} set to {}", key, label);
+    }
+    Ok(())
+}
+
+/// Editor for `key`, moved on to its current target for settings that first ask which
+/// device to operate on, e.g. the primary display for `display_resolution`
+fn editor_for(key: &str) -> Result<Box<dyn SettingEditor>> {
+    let editor = create_editor(key)
+        .with_context(|| format!("Unknown setting '{}', try one of: {}", key, editor_keys().join(", ")))?;
+    if !editor.can_read() {
+        return Ok(editor);
+    }
+    let current = editor.get_current_value()?;
+    Ok(editor.select_target(&current).unwrap_or(editor))
+}
+
+/// Keys of the editors in the catalog. Per-adapter editors are left out, as they are
+/// only known once the adapters are discovered (`adapter_toggle:<name>`, `adapter_dns:<name>`).
+fn editor_keys() -> Vec<&'static str> {
+    SETTINGS_ITEMS.iter().filter_map(|item| item.editor_key.as_deref()).collect()
+}
+
+/// Parse `text` into a value for `editor`: one of its options, or else a value of the
+/// kind its editor type takes
+fn parse_value(
+    editor: &dyn SettingEditor,
+    options: &[SettingOption],
+    current: Option<&SettingValue>,
+    text: &str,
+) -> Result<SettingValue> {
+    let text = text.trim();
+    let editor_type = editor.get_editor_type();
+    if let EditorType::MultiSelect = editor_type {
+        return parse_flags(options, current, text);
+    }
+    if let Some(option) = find_option(options, text) {
+        return Ok(option.value.clone());
+    }
+    match editor_type {
+        EditorType::Toggle => parse_bool(text)
+            .map(SettingValue::Bool)
+            .with_context(|| format!("'{}' is not on or off", text)),
+        EditorType::Slider { .. } | EditorType::NumberInput { .. } => {
+            // The unit the value is shown with may be typed along with it, e.g. "50%"
+            let number = editor
+                .value_format()
+                .and_then(|format| text.strip_suffix(format.unit))
+                .unwrap_or(text)
+                .trim();
+            let parsed = match current {
+                Some(SettingValue::Float(_)) => number.parse().ok().map(SettingValue::Float),
+                _ => number.parse().ok().map(SettingValue::Integer),
+            };
+            parsed.with_context(|| format!("'{}' is not a number", text))
+        }
+        EditorType::TextInput { .. } => Ok(SettingValue::String(text.to_string())),
+        // Presets like "Custom..." take free-form entry, e.g. typed DNS servers
+        EditorType::Dropdown if options.iter().any(|option| editor.custom_entry_editor(&option.value).is_some()) => {
+            Ok(SettingValue::String(text.to_string()))
+        }
+        _ if options.is_empty() => bail!("This setting can't be set from the command line"),
+        _ => bail!("'{}' is not one of: {}", text, labels(options)),
+    }
+}
+
+/// The option `text` names by label or value, ignoring case. Display modes can also be
+/// given as `WIDTHxHEIGHT` or `WIDTHxHEIGHT@REFRESH`, taking the highest refresh rate
+/// when it is left out.
+fn find_option<'a>(options: &'a [SettingOption], text: &str) -> Option<&'a SettingOption> {
+    let named = options.iter().find(|option| {
+        option.label.eq_ignore_ascii_case(text) || option.value.to_string().eq_ignore_ascii_case(text)
+    });
+    // Modes are listed highest refresh rate first, so the first match is the fastest
+    named.or_else(|| {
+        let (width, height, rate) = parse_display_mode(text)?;
+        options.iter().find(|option| match option.value {
+            SettingValue::Resolution { width: w, height: h, refresh } => {
+                (w, h) == (width, height) && rate.is_none_or(|rate| rate == refresh)
+            }
+            _ => false,
+        })
+    })
+}
+
+/// Width, height and refresh rate from e.g. `2560x1440`, `2560x1440@144` or `2560x1440@144Hz`
+fn parse_display_mode(text: &str) -> Option<(u32, u32, Option<u32>)> {
+    let (size, rate) = match text.split_once('@') {
+        Some((size, rate)) => {
+            let rate = rate.trim();
+            let rate = rate.strip_suffix("Hz").or_else(|| rate.strip_suffix("hz")).unwrap_or(rate);
+            (size, Some(rate.trim().parse().ok()?))
+        }
+        None => (text, None),
+    };
+    let (width, height) = size.split_once(['x', 'X', '×'])?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?, rate))
+}
+
+fn labels(options: &[SettingOption]) -> String {
+    options.iter().map(|option| option.label.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+fn parse_bool(text: &str) -> Option<bool> {
+    match text.to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "enabled" | "enable" | "1" => Some(true),
+        "off" | "false" | "no" | "disabled" | "disable" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Comma-separated entries of a multi-select, by label or id, to switch on, or off when
+/// prefixed with `-`. Entries left out keep their current state.
+fn parse_flags(options: &[SettingOption], current: Option<&SettingValue>, text: &str) -> Result<SettingValue> {
+    let mut flags = match current {
+        Some(SettingValue::Flags(flags)) => flags.clone(),
+        _ => BTreeMap::new(),
+    };
+    for entry in text.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (name, enabled) = match entry.strip_prefix('-') {
+            Some(name) => (name.trim(), false),
+            None => (entry.strip_prefix('+').unwrap_or(entry).trim(), true),
+        };
+        match find_option(options, name).map(|option| &option.value) {
+            Some(SettingValue::Selection(id)) => {
+                flags.insert(id.clone(), enabled);
+            }
+            _ => bail!("'{}' is not one of: {}", name, labels(options)),
+        }
+    }
+    Ok(SettingValue::Flags(flags))
+}
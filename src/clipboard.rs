@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use std::{thread, time::Duration};
+use windows::Win32::{
+    Foundation::{GlobalFree, HANDLE, HWND},
+    System::DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData},
+    System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+    System::Ole::CF_UNICODETEXT,
+};
+
+/// Attempts at opening the clipboard, which another program may be holding for a moment
+const OPEN_ATTEMPTS: usize = 5;
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// Put `text` on the Windows clipboard, replacing whatever was there
+pub fn set_text(text: &str) -> Result<()> {
+    open()?;
+    let result = unsafe { EmptyClipboard() }
+        .context("Could not clear the clipboard")
+        .and_then(|_| write_unicode(text));
+    // Closing can only fail if the clipboard wasn't open, which it is at this point
+    let _ = unsafe { CloseClipboard() };
+    result
+}
+
+fn open() -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match unsafe { OpenClipboard(HWND::default()) } {
+            Ok(()) => return Ok(()),
+            Err(_) if attempt < OPEN_ATTEMPTS => {
+                attempt += 1;
+                thread::sleep(OPEN_RETRY_DELAY);
+            }
+            Err(e) => return Err(e).context("The clipboard is in use by another program"),
+        }
+    }
+}
+
+/// Hand `text` to the open clipboard as `CF_UNICODETEXT`, which takes ownership of the
+/// memory once `SetClipboardData` succeeds
+fn write_unicode(text: &str) -> Result<()> {
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    let bytes = wide.len() * std::mem::size_of::<u16>();
+    unsafe {
+        let memory = GlobalAlloc(GMEM_MOVEABLE, bytes).context("Not enough memory to copy to the clipboard")?;
+        let target = GlobalLock(memory) as *mut u16;
+        if target.is_null() {
+            let _ = GlobalFree(memory);
+            anyhow::bail!("Could not write to clipboard memory");
+        }
+        std::ptr::copy_nonoverlapping(wide.as_ptr(), target, wide.len());
+        // Reports an error once the lock count drops to zero, which is the expected outcome
+        let _ = GlobalUnlock(memory);
+        if let Err(e) = SetClipboardData(CF_UNICODETEXT.0 as u32, HANDLE(memory.0 as isize)) {
+            let _ = GlobalFree(memory);
+            return Err(e).context("Could not copy to the clipboard");
+        }
+    }
+    Ok(())
+}
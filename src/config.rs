@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+use crate::{exec::CommandLog, settings::Category, ui::ListDensity};
+
+/// Folder holding everything TMWT persists, i.e. `%APPDATA%\TMWT`
+pub fn app_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("TMWT"))
+}
+
+/// User data persisted between runs in `%APPDATA%\TMWT\config.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub favorites: Vec<String>,
+    /// Names of the user's own choosing, by item name, shown in the list and matched by search
+    pub aliases: BTreeMap<String, String>,
+    /// Name of a built-in `Theme`, dark when unset
+    pub theme: Option<String>,
+    /// Whether moving past the end of a list jumps to its other end
+    pub wrap_navigation: bool,
+    /// Percent of the width given to the categories pane, 30 when unset
+    pub categories_width: Option<u16>,
+    /// Whether the items list shows descriptions under the names
+    pub density: ListDensity,
+    /// How much of each external command is written to the command log
+    pub command_log: CommandLog,
+    /// Milliseconds between ticks while nothing is animating, 250 when unset and kept
+    /// within 16-1000
+    pub tick_rate_ms: Option<u64>,
+    /// Whether repeats of a held key are dropped while earlier keys are still being handled
+    pub coalesce_key_repeats: bool,
+    /// Whether state is spelled out in words like "[on]" instead of glyphs and color,
+    /// for terminal screen readers
+    pub screen_reader: bool,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        app_dir().map(|dir| dir.join("config.json"))
+    }
+    
+    /// Load the saved config, falling back to defaults if it is missing or unreadable
+    pub fn load() -> Self {
+        load_json(Self::path())
+    }
+    
+    pub fn save(&self) -> Result<()> {
+        save_json(Self::path(), self)
+    }
+}
+
+/// Where the cursor was when TMWT last quit, in `%APPDATA%\TMWT\state.json`.
+/// Stored by name so reordering the lists doesn't misplace it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub category: Option<Category>,
+    pub item: Option<String>,
+    /// Submitted search queries, oldest first
+    pub search_history: Vec<String>,
+}
+
+impl UiState {
+    pub fn path() -> Option<PathBuf> {
+        app_dir().map(|dir| dir.join("state.json"))
+    }
+    
+    pub fn load() -> Self {
+        load_json(Self::path())
+    }
+    
+    pub fn save(&self) -> Result<()> {
+        save_json(Self::path(), self)
+    }
+}
+
+fn load_json<T: DeserializeOwned + Default>(path: Option<PathBuf>) -> T {
+    path.and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_json<T: Serialize>(path: Option<PathBuf>, value: &T) -> Result<()> {
+    let path = path.context("Could not locate the application data folder")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config folder")?;
+    }
+    let contents = serde_json::to_string_pretty(value)?;
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,314 @@
+use crate::settings_editor::{
+    from_wide, get_display_devices, CommandExt, EditorType, SettingEditor, SettingOption, SettingValue, ValueFormat,
+};
+use anyhow::{Context, Result};
+use std::process::Command;
+use windows::{
+    Win32::Devices::Display::{
+        DisplayConfigGetDeviceInfo, DisplayConfigSetDeviceInfo, GetDisplayConfigBufferSizes, QueryDisplayConfig,
+        DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME, DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_DEVICE_INFO_TYPE,
+        DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SOURCE_DEVICE_NAME, QDC_ONLY_ACTIVE_PATHS,
+    },
+    Win32::Foundation::{LUID, POINT},
+    Win32::Graphics::Gdi::{MonitorFromPoint, MONITOR_DEFAULTTOPRIMARY},
+    Win32::UI::HiDpi::{
+        GetDpiForMonitor, SetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+    },
+};
+
+/// Display brightness editor backed by WMI
+#[derive(Debug, Clone, Default)]
+pub struct BrightnessEditor;
+
+impl BrightnessEditor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    fn get_brightness(&self) -> Result<u8> {
+        let script = r#"
+            (Get-CimInstance -Namespace root/wmi -ClassName WmiMonitorBrightness -ErrorAction SilentlyContinue | Select-Object -First 1).CurrentBrightness
+        "#;
+        
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-Command", script])
+            .checked_output("Failed to query display brightness")?;
+        
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let brightness = output_str.trim();
+        
+        // Desktops with external monitors expose no WmiMonitorBrightness instance
+        if brightness.is_empty() {
+            anyhow::bail!("No display with controllable brightness was found");
+        }
+        
+        brightness
+            .parse::<u8>()
+            .with_context(|| format!("Unexpected brightness value: {}", brightness))
+    }
+}
+
+impl SettingEditor for BrightnessEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        Ok(SettingValue::Float(self.get_brightness()? as f64))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Float(level) = value {
+            let script = format!(
+                "Get-CimInstance -Namespace root/wmi -ClassName WmiMonitorBrightnessMethods | \
+                 Invoke-CimMethod -MethodName WmiSetBrightness -Arguments @{{Timeout=1; Brightness={}}}",
+                level.round().clamp(0.0, 100.0) as u8
+            );
+            
+            Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .checked_output("Failed to set display brightness")?;
+            
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for brightness")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Float(level) = value {
+            Ok((0.0..=100.0).contains(level))
+        } else {
+            Ok(false)
+        }
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Slider { min: 0.0, max: 100.0, step: 5.0 }
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        Some(ValueFormat::new(0, "%"))
+    }
+}
+
+/// Scale factors Windows steps through, in percent. The display driver reports
+/// scales as steps along this list relative to the recommended one.
+const DPI_SCALE_STEPS: [i64; 12] = [100, 125, 150, 175, 200, 225, 250, 300, 350, 400, 450, 500];
+
+/// Scale factors offered by the editor
+const STANDARD_SCALES: [i64; 5] = [100, 125, 150, 175, 200];
+
+/// Undocumented `DisplayConfigGetDeviceInfo`/`DisplayConfigSetDeviceInfo` request
+/// types that Settings uses to read and change a source's scale
+const DISPLAYCONFIG_DEVICE_INFO_GET_DPI_SCALE: DISPLAYCONFIG_DEVICE_INFO_TYPE = DISPLAYCONFIG_DEVICE_INFO_TYPE(-3);
+const DISPLAYCONFIG_DEVICE_INFO_SET_DPI_SCALE: DISPLAYCONFIG_DEVICE_INFO_TYPE = DISPLAYCONFIG_DEVICE_INFO_TYPE(-4);
+
+#[repr(C)]
+struct DpiScaleGet {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    /// Steps from the recommended scale to the smallest, largest and current one
+    min_scale_rel: i32,
+    cur_scale_rel: i32,
+    max_scale_rel: i32,
+}
+
+#[repr(C)]
+struct DpiScaleSet {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    scale_rel: i32,
+}
+
+/// Scales a display source supports, as indexes into `DPI_SCALE_STEPS`. The
+/// smallest is always 100%, as the recommended scale is counted from there.
+struct ScaleRange {
+    recommended: usize,
+    max: usize,
+}
+
+/// Display scaling of the primary display
+#[derive(Debug, Clone, Default)]
+pub struct ScalingEditor;
+
+impl ScalingEditor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Adapter and source id of the primary display in the display configuration
+    fn primary_source(&self) -> Result<(LUID, u32)> {
+        let primary = get_display_devices()?
+            .into_iter()
+            .find(|device| device.is_primary)
+            .context("No primary display found")?;
+        
+        let mut path_count = 0u32;
+        let mut mode_count = 0u32;
+        unsafe { GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count) }
+            .ok()
+            .context("Failed to query display configuration")?;
+        let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+        let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+        unsafe {
+            QueryDisplayConfig(
+                QDC_ONLY_ACTIVE_PATHS,
+                &mut path_count,
+                paths.as_mut_ptr(),
+                &mut mode_count,
+                modes.as_mut_ptr(),
+                None,
+            )
+        }
+        .ok()
+        .context("Failed to query display configuration")?;
+        paths.truncate(path_count as usize);
+        
+        // Match paths to the primary display through their GDI device name, e.g. \\.\DISPLAY1
+        for path in paths {
+            let mut source_name = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+                header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                    r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                    size: std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32,
+                    adapterId: path.sourceInfo.adapterId,
+                    id: path.sourceInfo.id,
+                },
+                ..Default::default()
+            };
+            if unsafe { DisplayConfigGetDeviceInfo(&mut source_name.header) } == 0
+                && from_wide(&source_name.viewGdiDeviceName) == primary.device_name
+            {
+                return Ok((path.sourceInfo.adapterId, path.sourceInfo.id));
+            }
+        }
+        anyhow::bail!("The primary display is not in the active display configuration")
+    }
+    
+    fn get_scale_range(&self) -> Result<ScaleRange> {
+        let (adapter_id, source_id) = self.primary_source()?;
+        let mut request = DpiScaleGet {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                r#type: DISPLAYCONFIG_DEVICE_INFO_GET_DPI_SCALE,
+                size: std::mem::size_of::<DpiScaleGet>() as u32,
+                adapterId: adapter_id,
+                id: source_id,
+            },
+            min_scale_rel: 0,
+            cur_scale_rel: 0,
+            max_scale_rel: 0,
+        };
+        if unsafe { DisplayConfigGetDeviceInfo(&mut request.header) } != 0 {
+            anyhow::bail!("Failed to read the supported display scales");
+        }
+        
+        let recommended = request.min_scale_rel.unsigned_abs() as usize;
+        Ok(ScaleRange {
+            recommended,
+            max: (recommended as i32 + request.max_scale_rel).clamp(0, DPI_SCALE_STEPS.len() as i32 - 1) as usize,
+        })
+    }
+    
+    fn is_supported(&self, scale: i64, range: &ScaleRange) -> bool {
+        DPI_SCALE_STEPS
+            .iter()
+            .position(|step| *step == scale)
+            .is_some_and(|index| index <= range.max)
+    }
+}
+
+impl SettingEditor for ScalingEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+        unsafe {
+            // Without per-monitor awareness every monitor reports the system DPI
+            let previous = SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
+            let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY);
+            let result = GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+            SetThreadDpiAwarenessContext(previous);
+            result.context("Failed to read display scaling")?;
+        }
+        // 96 DPI is 100%
+        Ok(SettingValue::Integer((dpi_x as i64 * 100 + 48) / 96))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        let range = self.get_scale_range()?;
+        Ok(STANDARD_SCALES
+            .into_iter()
+            .filter(|scale| self.is_supported(*scale, &range))
+            .map(|scale| SettingOption {
+                label: format!("{}%", scale),
+                value: SettingValue::Integer(scale),
+                description: (DPI_SCALE_STEPS[range.recommended] == scale).then(|| "Recommended".to_string()),
+            })
+            .collect())
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Integer(scale) = value {
+            let index = DPI_SCALE_STEPS
+                .iter()
+                .position(|step| *step == scale)
+                .with_context(|| format!("{}% is not a Windows scale factor", scale))?;
+            let range = self.get_scale_range()?;
+            let (adapter_id, source_id) = self.primary_source()?;
+            
+            let request = DpiScaleSet {
+                header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                    r#type: DISPLAYCONFIG_DEVICE_INFO_SET_DPI_SCALE,
+                    size: std::mem::size_of::<DpiScaleSet>() as u32,
+                    adapterId: adapter_id,
+                    id: source_id,
+                },
+                scale_rel: index as i32 - range.recommended as i32,
+            };
+            if unsafe { DisplayConfigSetDeviceInfo(&request.header) } != 0 {
+                anyhow::bail!("Failed to set display scaling to {}%", scale);
+            }
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for display scaling")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        let SettingValue::Integer(scale) = value else {
+            return Ok(false);
+        };
+        if !STANDARD_SCALES.contains(scale) {
+            return Ok(false);
+        }
+        let range = self.get_scale_range()?;
+        if !self.is_supported(*scale, &range) {
+            anyhow::bail!("This display doesn't support {}% scaling", scale);
+        }
+        Ok(true)
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Dropdown
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        Some(ValueFormat::new(0, "%"))
+    }
+    
+    fn apply_note(&self) -> Option<&'static str> {
+        Some("some apps only use the new scale after you sign out")
+    }
+}
@@ -1,107 +1,304 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, ScrollbarState, Wrap},
     Frame,
 };
+use std::{rc::Rc, time::Instant};
 use crate::{
-    app::EditState,
-    settings_editor::{EditorType, SettingOption, SettingValue},
+    app::{Confirmation, EditState, UndoRecord},
+    theme::Theme,
+    ui::draw_scrollbar,
+    settings_editor::{aspect_ratio, EditorType, SettingOption, SettingValue},
 };
 
-pub fn draw_edit_panel(f: &mut Frame, area: Rect, edit_state: &EditState) {
+/// Draw the edit panel for `edit_state`. With `screen_reader`, state that glyphs and color
+/// show otherwise, like which option is current, is spelled out in words.
+pub fn draw_edit_panel(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, screen_reader: bool) {
     // Clear the area first
     f.render_widget(Clear, area);
     
-    // Create the main block
+    // Create the main block, with the revert countdown while a change awaits confirmation
+    let title = match (&edit_state.confirmation, &edit_state.preview) {
+        _ if edit_state.saving => format!(" Editing: {} - applying... ", edit_state.item_name),
+        (Some(Confirmation::KeepChanges { deadline }), _) => format!(
+            " Editing: {} - reverting in {}s ",
+            edit_state.item_name,
+            seconds_left(*deadline)
+        ),
+        (_, Some(preview)) => format!(
+            " Editing: {} - previewing, restoring in {}s ",
+            edit_state.item_name,
+            seconds_left(preview.deadline)
+        ),
+        _ if !edit_state.editor.can_write() => format!(" Viewing: {} (read-only) ", edit_state.item_name),
+        _ if edit_state.staging => format!(" Editing: {} - saving stages the change ", edit_state.item_name),
+        _ => format!(" Editing: {} ", edit_state.item_name),
+    };
     let block = Block::default()
-        .title(format!(" Editing: {} ", edit_state.item_name))
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(theme.accent));
     
     let inner_area = block.inner(area);
     f.render_widget(block, area);
     
     // Split the inner area
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Current value
-            Constraint::Min(5),     // Editor area
-            Constraint::Length(4),  // Help text
-        ])
-        .split(inner_area);
+    let chunks = panel_layout(inner_area);
+    
+    if edit_state.loading {
+        draw_current_value(f, chunks[0], theme, "Loading...", None);
+        draw_loading(f, chunks[1], theme, edit_state.spinner_frame);
+        draw_edit_help(f, chunks[3], theme, edit_state);
+        return;
+    }
     
-    // Draw current value
-    draw_current_value(f, chunks[0], &edit_state.current_value);
+    // Draw current value and what saving would change it to
+    let current = edit_state.label_for(&edit_state.current_value);
+    let pending = edit_state.pending_value.as_ref().map(|value| {
+        (edit_state.label_for(value), value != &edit_state.current_value)
+    });
+    draw_current_value(f, chunks[0], theme, &current, pending);
     
     // Draw the editor based on type
     match &edit_state.editor_type {
-        EditorType::Toggle => draw_toggle_editor(f, chunks[1], edit_state),
-        EditorType::Dropdown => draw_dropdown_editor(f, chunks[1], edit_state),
-        EditorType::ResolutionPicker => draw_resolution_picker(f, chunks[1], edit_state),
+        // A picker with nothing to pick from would otherwise be a blank area
+        EditorType::Dropdown | EditorType::ResolutionPicker | EditorType::MultiSelect if edit_state.options.is_empty() => {
+            draw_no_options(f, chunks[1], theme)
+        }
+        EditorType::Toggle => draw_toggle_editor(f, chunks[1], theme, edit_state, screen_reader),
+        EditorType::Dropdown => draw_dropdown_editor(f, chunks[1], theme, edit_state, screen_reader),
+        EditorType::ResolutionPicker => draw_resolution_picker(f, chunks[1], theme, edit_state, screen_reader),
+        EditorType::MultiSelect => draw_multi_select_editor(f, chunks[1], theme, edit_state, screen_reader),
         EditorType::Slider { min, max, step } => {
-            draw_slider_editor(f, chunks[1], edit_state, *min, *max, *step)
+            draw_slider_editor(f, chunks[1], theme, edit_state, *min, *max, *step)
         }
-        _ => draw_unsupported_editor(f, chunks[1]),
+        EditorType::TextInput { multiline } => {
+            draw_text_input_editor(f, chunks[1], theme, edit_state, *multiline)
+        }
+        EditorType::NumberInput { min, max } => {
+            draw_number_input_editor(f, chunks[1], theme, edit_state, *min, *max)
+        }
+        _ => draw_unsupported_editor(f, chunks[1], theme),
+    }
+    
+    // Why the last save was rejected, right under the value it was about, or else
+    // why the chosen value may not work
+    if let Some(error) = &edit_state.error_message {
+        let marker = if screen_reader { "Error:" } else { "✗" };
+        let error = Paragraph::new(format!("{} {}", marker, error)).style(Style::default().fg(theme.error));
+        f.render_widget(error, chunks[2]);
+    } else if let Some(warning) = &edit_state.warning {
+        let marker = if screen_reader { "Warning:" } else { "⚠" };
+        let warning = Paragraph::new(format!("{} {}", marker, warning)).style(Style::default().fg(theme.highlight));
+        f.render_widget(warning, chunks[2]);
     }
     
     // Draw help text
-    draw_edit_help(f, chunks[2], &edit_state.editor_type);
+    draw_edit_help(f, chunks[3], theme, edit_state);
+}
+
+fn panel_layout(inner_area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Current value
+            Constraint::Min(5),     // Editor area
+            Constraint::Length(1),  // Validation error or warning
+            Constraint::Length(4),  // Help text
+        ])
+        .split(inner_area)
+}
+
+/// Area the editor itself gets inside a panel drawn at `area`
+pub fn editor_area(area: Rect) -> Rect {
+    panel_layout(area.inner(&Margin { vertical: 1, horizontal: 1 }))[1]
 }
 
-fn draw_current_value(f: &mut Frame, area: Rect, value: &SettingValue) {
-    let current = Paragraph::new(vec![
+pub fn draw_confirm_dialog(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, confirmation: &Confirmation) {
+    f.render_widget(Clear, area);
+    
+    let pending = edit_state
+        .pending_value
+        .as_ref()
+        .map(|value| edit_state.label_for(value))
+        .unwrap_or_default();
+    
+    let (title, mut lines) = match confirmation {
+        Confirmation::Apply => (
+            " Confirm Change ",
+            vec![
+                Line::from(format!(
+                    "{} \"{}\" to {}?",
+                    if edit_state.staging { "Stage" } else { "Apply" },
+                    pending,
+                    edit_state.item_name
+                )),
+                Line::from(Span::styled(
+                    "This change needs administrator rights or may interrupt your session.",
+                    Style::default().fg(theme.muted),
+                )),
+            ],
+        ),
+        Confirmation::KeepChanges { deadline } => (
+            " Keep Changes? ",
+            vec![
+                Line::from(format!("Keep \"{}\" for {}?", pending, edit_state.item_name)),
+                Line::from(Span::styled(
+                    format!("Reverting in {} seconds...", seconds_left(*deadline)),
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+                )),
+            ],
+        ),
+        Confirmation::DiscardAndQuit => (
+            " Quit? ",
+            vec![
+                Line::from("Discard unsaved changes?"),
+                Line::from(Span::styled(
+                    format!("{} would have been set to \"{}\".", edit_state.item_name, pending),
+                    Style::default().fg(theme.muted),
+                )),
+            ],
+        ),
+    };
+    
+    lines.push(Line::from(""));
+    lines.push(match confirmation {
+        Confirmation::Apply | Confirmation::DiscardAndQuit => Line::from(vec![
+            Span::styled("[y] ", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+            Span::raw("Yes    "),
+            Span::styled("[n] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+            Span::raw("No"),
+        ]),
+        Confirmation::KeepChanges { .. } => Line::from(vec![
+            Span::styled("[Any key] ", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+            Span::raw("Keep    "),
+            Span::styled("[n/Esc] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+            Span::raw("Revert"),
+        ]),
+    });
+    
+    draw_dialog(f, area, theme, title, lines);
+}
+
+/// Ask before undoing a change that needs administrator rights or may interrupt the session,
+/// like saving it asked
+pub fn draw_undo_confirm_dialog(f: &mut Frame, area: Rect, theme: &Theme, record: &UndoRecord) {
+    f.render_widget(Clear, area);
+    
+    let lines = vec![
+        Line::from(format!("Restore {} to \"{}\"?", record.item_name, record.previous_label)),
+        Line::from(Span::styled(
+            "This change needs administrator rights or may interrupt your session.",
+            Style::default().fg(theme.muted),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y] ", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+            Span::raw("Yes    "),
+            Span::styled("[n] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+            Span::raw("No"),
+        ]),
+    ];
+    draw_dialog(f, area, theme, " Confirm Undo ", lines);
+}
+
+fn draw_dialog(f: &mut Frame, area: Rect, theme: &Theme, title: &str, lines: Vec<Line>) {
+    let dialog = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error)),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    
+    f.render_widget(dialog, area);
+}
+
+/// `pending` is the label of the value that saving would apply and whether it differs from `value`
+fn draw_current_value(f: &mut Frame, area: Rect, theme: &Theme, value: &str, pending: Option<(String, bool)>) {
+    let mut lines = vec![
         Line::from(vec![
             Span::raw("Current: "),
             Span::styled(
-                value.to_string(),
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                value,
+                Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD),
             ),
         ]),
-    ])
+    ];
+    if let Some((pending, changed)) = pending {
+        let line = if changed {
+            Line::from(vec![
+                Span::raw("Change:  "),
+                Span::raw(value.to_string()),
+                Span::raw(" → "),
+                Span::styled(pending, Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            ])
+        } else {
+            Line::from(Span::styled(
+                format!("Change:  {} → {} (unchanged)", value, pending),
+                Style::default().fg(theme.muted),
+            ))
+        };
+        lines.push(line);
+    }
+    
+    let current = Paragraph::new(lines)
     .block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
     
     f.render_widget(current, area);
 }
 
-fn draw_toggle_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
+fn draw_toggle_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, screen_reader: bool) {
     let is_enabled = matches!(&edit_state.pending_value, Some(SettingValue::Bool(true)));
     
+    // Only the chosen state, so nothing but the state itself is read out
+    if screen_reader {
+        let (state, color) = if is_enabled { ("[on] Enabled", theme.success) } else { ("[off] Disabled", theme.error) };
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![Span::raw("  "), Span::styled(state, Style::default().fg(color).add_modifier(Modifier::BOLD))]),
+        ]);
+        f.render_widget(paragraph, area);
+        return;
+    }
+    
     let toggle_text = vec![
         Line::from(""),
         Line::from(vec![
             Span::raw("  "),
             if is_enabled {
-                Span::styled("● ", Style::default().fg(Color::Green))
+                Span::styled("● ", Style::default().fg(theme.success))
             } else {
                 Span::raw("○ ")
             },
             Span::styled(
                 "Enabled",
                 if is_enabled {
-                    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(theme.muted)
                 },
             ),
             Span::raw("    "),
             if !is_enabled {
-                Span::styled("● ", Style::default().fg(Color::Red))
+                Span::styled("● ", Style::default().fg(theme.error))
             } else {
                 Span::raw("○ ")
             },
             Span::styled(
                 "Disabled",
                 if !is_enabled {
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(theme.muted)
                 },
             ),
         ]),
@@ -113,7 +310,7 @@ fn draw_toggle_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
     f.render_widget(paragraph, area);
 }
 
-fn draw_dropdown_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
+fn draw_dropdown_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, screen_reader: bool) {
     let items: Vec<ListItem> = edit_state
         .options
         .iter()
@@ -123,10 +320,10 @@ fn draw_dropdown_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
             let is_current = Some(&opt.value) == edit_state.pending_value.as_ref();
             
             let mut spans = vec![
-                if is_current {
-                    Span::styled("► ", Style::default().fg(Color::Green))
-                } else {
-                    Span::raw("  ")
+                match (is_current, screen_reader) {
+                    (true, false) => Span::styled("► ", Style::default().fg(theme.success)),
+                    (true, true) => Span::styled("[current] ", Style::default().fg(theme.success)),
+                    (false, _) => Span::raw("  "),
                 },
                 Span::raw(&opt.label),
             ];
@@ -134,14 +331,14 @@ fn draw_dropdown_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
             if let Some(desc) = &opt.description {
                 spans.push(Span::styled(
                     format!(" - {}", desc),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ));
             }
             
             let style = if is_selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
             } else if is_current {
-                Style::default().fg(Color::Green)
+                Style::default().fg(theme.success)
             } else {
                 Style::default()
             };
@@ -157,7 +354,7 @@ fn draw_dropdown_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("→ ");
@@ -170,68 +367,237 @@ fn draw_dropdown_editor(f: &mut Frame, area: Rect, edit_state: &EditState) {
     );
 }
 
-fn draw_resolution_picker(f: &mut Frame, area: Rect, edit_state: &EditState) {
-    // Group resolutions by aspect ratio
+fn draw_multi_select_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, screen_reader: bool) {
+    let flags = match &edit_state.pending_value {
+        Some(SettingValue::Flags(flags)) => Some(flags),
+        _ => None,
+    };
+    
+    let items: Vec<ListItem> = edit_state
+        .options
+        .iter()
+        .enumerate()
+        .map(|(i, opt)| {
+            let is_selected = i == edit_state.selected_option_index;
+            let is_enabled = match &opt.value {
+                SettingValue::Selection(id) => flags.and_then(|flags| flags.get(id)).copied().unwrap_or(false),
+                _ => false,
+            };
+            
+            let mut spans = vec![
+                match (is_enabled, screen_reader) {
+                    (true, false) => Span::styled("[✓] ", Style::default().fg(theme.success)),
+                    (false, false) => Span::styled("[ ] ", Style::default().fg(theme.muted)),
+                    (true, true) => Span::styled("[on] ", Style::default().fg(theme.success)),
+                    (false, true) => Span::styled("[off] ", Style::default().fg(theme.muted)),
+                },
+                Span::raw(&opt.label),
+            ];
+            
+            if let Some(desc) = &opt.description {
+                spans.push(Span::styled(
+                    format!(" - {}", desc),
+                    Style::default().fg(theme.muted),
+                ));
+            }
+            
+            let style = if is_selected {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+    
+    if items.is_empty() {
+        let empty = Paragraph::new("Nothing to list")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, area);
+        return;
+    }
+    
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("→ ");
+    
+    f.render_stateful_widget(
+        list,
+        area,
+        &mut ratatui::widgets::ListState::default()
+            .with_selected(Some(edit_state.selected_option_index)),
+    );
+}
+
+enum PickerRow<'a> {
+    RecentHeading,
+    /// Mode applied earlier this session, labelled with its resolution as it has no heading
+    RecentMode(&'a SettingOption),
+    AspectHeading(String),
+    ResolutionHeading(u32, u32),
+    Mode(&'a SettingOption),
+    Separator,
+}
+
+/// Lines of the resolution picker: the first `recent` options on their own, then modes grouped
+/// by aspect ratio, each resolution heading its refresh rates
+fn resolution_picker_rows(options: &[SettingOption], recent: usize) -> Vec<PickerRow<'_>> {
+    let (recent, options) = options.split_at(recent.min(options.len()));
+    // Options arrive sorted by aspect ratio, so grouping keeps the option order intact
     let mut grouped: std::collections::BTreeMap<String, Vec<&SettingOption>> = 
         std::collections::BTreeMap::new();
     
-    for opt in &edit_state.options {
-        if let SettingValue::Resolution { width, height } = &opt.value {
-            let ratio = gcd(*width, *height);
-            let aspect = format!("{}:{}", width / ratio, height / ratio);
-            grouped.entry(aspect).or_insert_with(Vec::new).push(opt);
+    for opt in options {
+        if let SettingValue::Resolution { width, height, .. } = &opt.value {
+            grouped.entry(aspect_ratio(*width, *height)).or_default().push(opt);
         }
     }
     
+    let mut rows = vec![];
+    if !recent.is_empty() {
+        rows.push(PickerRow::RecentHeading);
+        rows.extend(recent.iter().map(PickerRow::RecentMode));
+        rows.push(PickerRow::Separator);
+    }
+    for (aspect, modes) in grouped {
+        rows.push(PickerRow::AspectHeading(aspect));
+        
+        let mut last_resolution = None;
+        for mode in modes {
+            let SettingValue::Resolution { width, height, .. } = &mode.value else {
+                continue;
+            };
+            if last_resolution != Some((*width, *height)) {
+                rows.push(PickerRow::ResolutionHeading(*width, *height));
+                last_resolution = Some((*width, *height));
+            }
+            rows.push(PickerRow::Mode(mode));
+        }
+        
+        rows.push(PickerRow::Separator);
+    }
+    rows
+}
+
+/// Line range to keep visible for the mode at `index`: from the headings directly above it down to the mode itself
+pub fn resolution_picker_lines(options: &[SettingOption], recent: usize, index: usize) -> (usize, usize) {
+    let rows = resolution_picker_rows(options, recent);
+    let Some(line) = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| matches!(row, PickerRow::Mode(_) | PickerRow::RecentMode(_)))
+        .nth(index)
+        .map(|(line, _)| line)
+    else {
+        return (0, 0);
+    };
+    let headings_above = rows[..line]
+        .iter()
+        .rev()
+        .take_while(|row| {
+            matches!(row, PickerRow::RecentHeading | PickerRow::AspectHeading(_) | PickerRow::ResolutionHeading(..))
+        })
+        .count();
+    (line - headings_above, line)
+}
+
+fn draw_resolution_picker(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, screen_reader: bool) {
     let mut lines = vec![];
     let mut item_index = 0;
     
-    for (aspect, resolutions) in grouped {
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("  {} ", aspect),
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED),
-            ),
-        ]));
-        
-        for res in resolutions {
-            let is_selected = item_index == edit_state.selected_option_index;
-            let is_current = Some(&res.value) == edit_state.pending_value.as_ref();
-            
-            let prefix = if is_current { "► " } else { "  " };
-            let style = if is_selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else if is_current {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default()
-            };
-            
-            lines.push(Line::from(vec![
-                Span::raw("    "),
-                Span::styled(prefix, style),
-                Span::styled(&res.label, style),
-                if let Some(desc) = &res.description {
-                    Span::styled(format!(" ({})", desc), Style::default().fg(Color::DarkGray))
+    for row in resolution_picker_rows(&edit_state.options, edit_state.recent_options) {
+        let recent = matches!(row, PickerRow::RecentMode(_));
+        match row {
+            PickerRow::RecentHeading => {
+                lines.push(Line::from(vec![
+                    Span::styled("  Recent ", Style::default().fg(theme.highlight).add_modifier(Modifier::UNDERLINED)),
+                ]));
+            }
+            PickerRow::AspectHeading(aspect) => {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {} ", aspect),
+                        Style::default().fg(theme.highlight).add_modifier(Modifier::UNDERLINED),
+                    ),
+                ]));
+            }
+            // Resolution heading with its refresh rates listed beneath it
+            PickerRow::ResolutionHeading(width, height) => {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(format!("{} × {}", width, height), Style::default().add_modifier(Modifier::BOLD)),
+                ]));
+            }
+            PickerRow::Mode(mode) | PickerRow::RecentMode(mode) => {
+                let SettingValue::Resolution { width, height, refresh } = &mode.value else {
+                    continue;
+                };
+                
+                let is_selected = item_index == edit_state.selected_option_index;
+                let is_current = Some(&mode.value) == edit_state.pending_value.as_ref();
+                
+                let prefix = match (is_current, screen_reader) {
+                    (true, false) => "► ",
+                    (true, true) => "[current] ",
+                    (false, _) => "  ",
+                };
+                let style = if is_selected {
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+                } else if is_current {
+                    Style::default().fg(theme.success)
                 } else {
-                    Span::raw("")
-                },
-            ]));
-            
-            item_index += 1;
+                    Style::default()
+                };
+                
+                let (indent, label) = if recent {
+                    ("    ", format!("{} × {} @ {} Hz", width, height, refresh))
+                } else {
+                    ("      ", format!("{} Hz", refresh))
+                };
+                
+                lines.push(Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(prefix, style),
+                    Span::styled(label, style),
+                    if let Some(desc) = &mode.description {
+                        Span::styled(format!(" ({})", desc), Style::default().fg(theme.muted))
+                    } else {
+                        Span::raw("")
+                    },
+                ]));
+                
+                item_index += 1;
+            }
+            PickerRow::Separator => lines.push(Line::from("")), // Empty line between groups
         }
-        
-        lines.push(Line::from("")); // Empty line between groups
+    }
+    
+    let total_lines = lines.len();
+    let visible = area.height as usize;
+    let mut text_area = area;
+    if total_lines > visible {
+        // Positions run from the first line at the top to the last line at the bottom
+        let mut state = ScrollbarState::new(total_lines - visible + 1)
+            .viewport_content_length(visible)
+            .position(edit_state.scroll_offset as usize);
+        draw_scrollbar(f, area, &mut state, theme);
+        text_area.width = text_area.width.saturating_sub(1);
     }
     
     let paragraph = Paragraph::new(lines)
         .wrap(Wrap { trim: true })
         .scroll((edit_state.scroll_offset, 0));
     
-    f.render_widget(paragraph, area);
+    f.render_widget(paragraph, text_area);
 }
 
-fn draw_slider_editor(f: &mut Frame, area: Rect, edit_state: &EditState, min: f64, max: f64, _step: f64) {
+fn draw_slider_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, min: f64, max: f64, _step: f64) {
     let current_val = match &edit_state.pending_value {
         Some(SettingValue::Float(v)) => *v,
         Some(SettingValue::Integer(v)) => *v as f64,
@@ -239,6 +605,11 @@ fn draw_slider_editor(f: &mut Frame, area: Rect, edit_state: &EditState, min: f6
     };
     
     let percentage = ((current_val - min) / (max - min) * 100.0) as u16;
+    let format = edit_state.editor.value_format();
+    let number = |value: f64, precision: usize| match format {
+        Some(format) => format.number(value),
+        None => format!("{:.*}", precision, value),
+    };
     
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -254,8 +625,8 @@ fn draw_slider_editor(f: &mut Frame, area: Rect, edit_state: &EditState, min: f6
         Line::from(vec![
             Span::raw("Value: "),
             Span::styled(
-                format!("{:.1}", current_val),
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                number(current_val, 1),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
             ),
         ]),
     ])
@@ -273,9 +644,9 @@ fn draw_slider_editor(f: &mut Frame, area: Rect, edit_state: &EditState, min: f6
         ])
         .split(chunks[1]);
     
-    let min_label = Paragraph::new(format!("{:.0}", min))
+    let min_label = Paragraph::new(number(min, 0))
         .alignment(Alignment::Right);
-    let max_label = Paragraph::new(format!("{:.0}", max))
+    let max_label = Paragraph::new(number(max, 0))
         .alignment(Alignment::Left);
     
     f.render_widget(min_label, slider_chunks[0]);
@@ -297,19 +668,176 @@ fn draw_slider_editor(f: &mut Frame, area: Rect, edit_state: &EditState, min: f6
     bar.push('▌');
     
     let slider_bar = Paragraph::new(bar)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(theme.highlight))
         .alignment(Alignment::Center);
     
     f.render_widget(slider_bar, slider_chunks[1]);
 }
 
-fn draw_unsupported_editor(f: &mut Frame, area: Rect) {
+fn draw_text_input_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, multiline: bool) {
+    let text = match &edit_state.pending_value {
+        Some(SettingValue::String(s)) => s.as_str(),
+        _ => "",
+    };
+    
+    let cursor_style = Style::default().fg(theme.on_accent).bg(theme.accent);
+    let mut lines = vec![];
+    let mut spans = vec![];
+    let mut buffer = String::new();
+    
+    for (i, c) in text.chars().enumerate() {
+        if i == edit_state.cursor_position {
+            spans.push(Span::raw(std::mem::take(&mut buffer)));
+            // Show the cursor on the newline itself so it stays visible at line ends
+            let cursor_char = if c == '\n' { " ".to_string() } else { c.to_string() };
+            spans.push(Span::styled(cursor_char, cursor_style));
+            if c == '\n' {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+            continue;
+        }
+        if c == '\n' {
+            spans.push(Span::raw(std::mem::take(&mut buffer)));
+            lines.push(Line::from(std::mem::take(&mut spans)));
+        } else {
+            buffer.push(c);
+        }
+    }
+    
+    spans.push(Span::raw(buffer));
+    if edit_state.cursor_position >= text.chars().count() {
+        spans.push(Span::styled(" ", cursor_style));
+    }
+    lines.push(Line::from(spans));
+    
+    // Matching presets go on a line under the field, which only appears when there are some
+    let suggestions = edit_state.suggestions();
+    let (input_area, suggestion_area) = if suggestions.is_empty() {
+        (area, None)
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    };
+    
+    let title = if multiline { " Text (multiline) " } else { " Text " };
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight)),
+        )
+        .wrap(Wrap { trim: false });
+    
+    f.render_widget(paragraph, input_area);
+    
+    if let Some(suggestion_area) = suggestion_area {
+        let mut spans = vec![Span::styled(" [Tab] ", Style::default().fg(theme.muted))];
+        for (i, (value, label)) in suggestions.iter().enumerate() {
+            let style = if i == 0 {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.muted)
+            };
+            if i > 0 {
+                spans.push(Span::styled("  ·  ", Style::default().fg(theme.muted)));
+            }
+            spans.push(Span::styled(value.to_string(), style));
+            spans.push(Span::styled(format!(" ({})", label), Style::default().fg(theme.muted)));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), suggestion_area);
+    }
+}
+
+fn draw_number_input_editor(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState, min: Option<i64>, max: Option<i64>) {
+    let value = match &edit_state.pending_value {
+        Some(SettingValue::Integer(v)) => Some(*v),
+        _ => None,
+    };
+    
+    // Highlight whichever bound an out-of-range entry has been clamped to
+    let pinned = edit_state.pinned_bound();
+    let bound_span = |label: &str, bound: Option<i64>| {
+        match bound {
+            Some(b) => Span::styled(
+                format!("{}: {}", label, b),
+                if Some(b) == pinned {
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.muted)
+                },
+            ),
+            None => Span::styled(format!("{}: none", label), Style::default().fg(theme.muted)),
+        }
+    };
+    
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("▲ ", Style::default().fg(theme.muted)),
+            Span::styled(
+                // Shown as typed until there is a number to format, e.g. after deleting every digit
+                value.map_or_else(
+                    || format!("{}_", edit_state.number_text),
+                    |value| SettingValue::Integer(value).display_with(edit_state.editor.value_format()),
+                ),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ▼", Style::default().fg(theme.muted)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            bound_span("Min", min),
+            Span::raw("    "),
+            bound_span("Max", max),
+        ]),
+    ];
+    
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center);
+    
+    f.render_widget(paragraph, area);
+}
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+fn draw_loading(f: &mut Frame, area: Rect, theme: &Theme, frame: usize) {
+    let loading = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{} ", SPINNER[frame % SPINNER.len()]),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("Loading options...", Style::default().fg(theme.muted)),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(loading, area);
+}
+
+fn draw_no_options(f: &mut Frame, area: Rect, theme: &Theme) {
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled("No options available for this setting.", Style::default().fg(theme.error))),
+        Line::from(""),
+        Line::from("Windows reported nothing to choose from, so the current value is kept."),
+    ];
+    
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    
+    f.render_widget(paragraph, area);
+}
+
+fn draw_unsupported_editor(f: &mut Frame, area: Rect, theme: &Theme) {
     let text = vec![
         Line::from(""),
         Line::from(vec![
             Span::styled(
                 "This setting type is not yet supported for inline editing.",
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.error),
             ),
         ]),
         Line::from(""),
@@ -323,35 +851,56 @@ fn draw_unsupported_editor(f: &mut Frame, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-fn draw_edit_help(f: &mut Frame, area: Rect, editor_type: &EditorType) {
-    let help_text = match editor_type {
+fn draw_edit_help(f: &mut Frame, area: Rect, theme: &Theme, edit_state: &EditState) {
+    let help_text = match &edit_state.editor_type {
+        // Nothing can be saved, so the only thing left to do is leave
+        _ if !edit_state.editor.can_write() => {
+            "Read-only - this setting can't be changed from TMWT yet  [Esc] Close"
+        }
+        EditorType::Dropdown | EditorType::ResolutionPicker | EditorType::MultiSelect if edit_state.options.is_empty() => {
+            "[Esc] Cancel"
+        }
         EditorType::Toggle => {
             "[Space/Enter] Toggle  [Esc] Cancel  [S] Save"
         }
-        EditorType::Dropdown | EditorType::ResolutionPicker => {
+        EditorType::Dropdown => {
             "[↑↓] Navigate  [Enter] Select  [Esc] Cancel  [S] Save"
         }
+        EditorType::ResolutionPicker => {
+            "[↑↓] Navigate  [Enter] Select  [P] Preview live  [Esc] Cancel  [S] Save"
+        }
+        EditorType::MultiSelect => {
+            "[↑↓] Navigate  [Space/Enter] Switch on/off  [Esc] Cancel  [S] Save"
+        }
         EditorType::Slider { .. } => {
             "[←→] Adjust  [Shift+←→] Fine  [Esc] Cancel  [S] Save"
         }
+        EditorType::NumberInput { .. } => {
+            "[↑↓] ±1  [PgUp/PgDn] ±10  [0-9] Type  [Esc] Cancel  [S] Save"
+        }
+        EditorType::TextInput { multiline: true } => {
+            "Type to edit  [Enter] New line  [←→] Move  [Esc] Cancel  [Ctrl+S] Save"
+        }
+        EditorType::TextInput { multiline: false } => {
+            "Type to edit  [Tab] Complete  [←→] Move  [Backspace] Delete  [Esc] Cancel  [Ctrl+S] Save"
+        }
         _ => {
             "[Enter] Open Windows Settings  [Esc] Cancel"
         }
     };
     
     let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .block(
             Block::default()
                 .borders(Borders::TOP)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.muted)),
         )
         .alignment(Alignment::Center);
     
     f.render_widget(help, area);
 }
 
-// Helper function for GCD calculation
-fn gcd(a: u32, b: u32) -> u32 {
-    if b == 0 { a } else { gcd(b, a % b) }
-}
\ No newline at end of file
+fn seconds_left(deadline: Instant) -> u64 {
+    deadline.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64
+}
@@ -1,57 +1,190 @@
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
+use crossterm::event::{
+    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 use std::{
-    sync::mpsc,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
     thread,
     time::{Duration, Instant},
 };
+use crate::{
+    network_editor::NetworkAdapter,
+    settings_editor::{DisplayModeSnapshot, SettingOption, SettingValue},
+    snapshot::ApplyResult,
+};
 
 #[derive(Clone, Debug)]
 pub enum Event {
     Tick,
     Key(KeyEvent),
     Mouse(MouseEvent),
+    /// Second left click on the same cell within `InputOptions::double_click_time`,
+    /// sent right after that click's `Mouse` event
+    DoubleClick(MouseEvent),
     Resize(u16, u16),
+    EditorLoaded(EditorLoaded),
+    /// Current value of an item, for the detail pane and the item list
+    DetailLoaded {
+        item_name: String,
+        result: Result<String, String>,
+    },
+    SnapshotExported(Result<PathBuf, String>),
+    /// Text put on the clipboard by the copy key
+    ValueCopied(Result<String, String>),
+    /// Warning about the value chosen in an edit panel, see `SettingEditor::check_value`
+    ValueChecked {
+        /// The edit panel's request, as for `EditorLoaded`
+        request: u64,
+        value: SettingValue,
+        warning: Option<String>,
+    },
+    /// A value saved from an edit panel, with the display mode from before if one was captured,
+    /// boxed as it is far larger than the other events
+    ValueSaved {
+        /// The edit panel's request, as for `EditorLoaded`
+        request: u64,
+        result: Result<Option<Box<DisplayModeSnapshot>>, String>,
+    },
+    /// An undone change, naming the item and the label of the value it went back to
+    ChangeUndone {
+        item_name: String,
+        label: String,
+        result: Result<(), String>,
+    },
+    /// A display mode applied while previewing it
+    ValuePreviewed {
+        /// The edit panel's request, as for `EditorLoaded`
+        request: u64,
+        value: SettingValue,
+        result: Result<(), String>,
+    },
+    /// The display mode from before a preview, put back
+    PreviewRestored(Result<(), String>),
+    BatchApplied(Vec<ApplyResult>),
+    /// Name of the captured profile and how many settings it holds
+    ProfileCaptured(Result<(String, usize), String>),
+    /// Network adapters found on the system, each of which gets its own items
+    AdaptersListed(Result<Vec<NetworkAdapter>, String>),
+}
+
+/// Current value and options fetched by the worker for an edit panel
+#[derive(Clone, Debug)]
+pub struct EditorLoaded {
+    /// Matches `App`'s latest request, so results for a panel that was since closed are dropped
+    pub request: u64,
+    pub result: Result<(SettingValue, Vec<SettingOption>), String>,
+}
+
+/// How raw terminal input is turned into events
+#[derive(Debug, Clone, Copy)]
+pub struct InputOptions {
+    /// Drop repeats of a held key while earlier key events are still waiting to be
+    /// handled, so letting go of the key stops the selection right away
+    pub coalesce_key_repeats: bool,
+    /// Longest gap between two clicks that still makes them a double-click
+    pub double_click_time: Duration,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            coalesce_key_repeats: false,
+            double_click_time: Duration::from_millis(500),
+        }
+    }
 }
 
 pub struct EventHandler {
     sender: mpsc::Sender<Event>,
     receiver: mpsc::Receiver<Event>,
-    handler: thread::JoinHandle<()>,
+    /// Polling thread, taken when the handler is dropped to wait for it to exit
+    handler: Option<thread::JoinHandle<()>>,
+    running: Arc<AtomicBool>,
+    /// Milliseconds between `Event::Tick`s, read by the polling thread on every pass
+    tick_rate: Arc<AtomicU64>,
+    /// Key events sent but not yet taken by `next`, used to coalesce repeats
+    pending_keys: Arc<AtomicUsize>,
 }
 
 impl EventHandler {
-    pub fn new(tick_rate: Duration) -> Self {
+    pub fn new(tick_rate: Duration, options: InputOptions) -> Self {
         let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let tick_rate = Arc::new(AtomicU64::new(tick_rate.as_millis() as u64));
+        let pending_keys = Arc::new(AtomicUsize::new(0));
         let handler = {
             let sender = sender.clone();
+            let running = running.clone();
+            let tick_rate = tick_rate.clone();
+            let pending_keys = pending_keys.clone();
             thread::spawn(move || {
                 let mut last_tick = Instant::now();
-                loop {
+                let mut last_key: Option<KeyEvent> = None;
+                let mut last_click: Option<(Instant, u16, u16)> = None;
+                while running.load(Ordering::Relaxed) {
+                    let tick_rate = Duration::from_millis(tick_rate.load(Ordering::Relaxed));
+                    
                     // Poll for events
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
                         .unwrap_or_else(|| Duration::from_secs(0));
                     
-                    if event::poll(timeout).expect("Unable to poll for events") {
-                        match event::read().expect("Unable to read event") {
-                            CrosstermEvent::Key(e) => {
-                                if e.kind == event::KeyEventKind::Press {
-                                    sender.send(Event::Key(e)).expect("Failed to send key event");
+                    // A failed poll/read or a dropped receiver means the app is going away
+                    let ready = match event::poll(timeout) {
+                        Ok(ready) => ready,
+                        Err(_) => break,
+                    };
+                    if ready {
+                        let sent = match event::read() {
+                            Ok(CrosstermEvent::Key(e)) if e.kind != KeyEventKind::Release => {
+                                // Windows consoles report a held key as repeated presses, other
+                                // terminals as `Repeat`; both are treated the same. Typed characters
+                                // are never taken for repeats, so fast typing isn't lost.
+                                let is_repeat = e.kind == KeyEventKind::Repeat
+                                    || (!matches!(e.code, KeyCode::Char(_))
+                                        && last_key.is_some_and(|last| last.code == e.code && last.modifiers == e.modifiers));
+                                last_key = Some(e);
+                                if options.coalesce_key_repeats && is_repeat && pending_keys.load(Ordering::Relaxed) > 0 {
+                                    Ok(())
+                                } else {
+                                    pending_keys.fetch_add(1, Ordering::Relaxed);
+                                    sender.send(Event::Key(e))
                                 }
                             }
-                            CrosstermEvent::Mouse(e) => {
-                                sender.send(Event::Mouse(e)).expect("Failed to send mouse event");
+                            Ok(CrosstermEvent::Mouse(e)) => {
+                                let is_double_click = e.kind == MouseEventKind::Down(MouseButton::Left) && {
+                                    let is_double_click = last_click.is_some_and(|(at, column, row)| {
+                                        column == e.column && row == e.row && at.elapsed() < options.double_click_time
+                                    });
+                                    // A third click starts a new pair instead of making another double-click
+                                    last_click = if is_double_click { None } else { Some((Instant::now(), e.column, e.row)) };
+                                    is_double_click
+                                };
+                                sender.send(Event::Mouse(e)).and_then(|()| {
+                                    if is_double_click {
+                                        sender.send(Event::DoubleClick(e))
+                                    } else {
+                                        Ok(())
+                                    }
+                                })
                             }
-                            CrosstermEvent::Resize(w, h) => {
-                                sender.send(Event::Resize(w, h)).expect("Failed to send resize event");
-                            }
-                            _ => {}
+                            Ok(CrosstermEvent::Resize(w, h)) => sender.send(Event::Resize(w, h)),
+                            Ok(_) => Ok(()),
+                            Err(_) => break,
+                        };
+                        if sent.is_err() {
+                            break;
                         }
                     }
                     
                     if last_tick.elapsed() >= tick_rate {
-                        sender.send(Event::Tick).expect("Failed to send tick event");
+                        if sender.send(Event::Tick).is_err() {
+                            break;
+                        }
                         last_tick = Instant::now();
                     }
                 }
@@ -61,11 +194,39 @@ impl EventHandler {
         Self {
             sender,
             receiver,
-            handler,
+            handler: Some(handler),
+            running,
+            tick_rate,
+            pending_keys,
         }
     }
     
+    /// Change how often `Event::Tick` is sent, taking effect after the current wait
+    pub fn set_tick_rate(&self, tick_rate: Duration) {
+        self.tick_rate.store(tick_rate.as_millis() as u64, Ordering::Relaxed);
+    }
+    
+    /// Sender for background work to report results through the event loop
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
+    
     pub fn next(&self) -> Result<Event> {
-        Ok(self.receiver.recv()?)
+        let event = self.receiver.recv()?;
+        if matches!(event, Event::Key(_)) {
+            let _ = self.pending_keys.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| count.checked_sub(1));
+        }
+        Ok(event)
+    }
+}
+
+/// Stops the polling thread and waits for it to exit however the event loop ends, including
+/// on errors, so it never reads input meant for the shell the terminal is handed back to
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handler) = self.handler.take() {
+            let _ = handler.join();
+        }
     }
-}
\ No newline at end of file
+}
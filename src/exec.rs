@@ -0,0 +1,190 @@
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::{Child, Command, Output},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// How much of each external command goes into the command log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandLog {
+    /// Nothing is written
+    #[default]
+    Off,
+    /// Program and exit code only, leaving out arguments and output that can
+    /// hold DNS servers, adapter or computer names
+    Summary,
+    /// Program, arguments, exit code and everything the command printed
+    Full,
+}
+
+/// Log file size at which it is rotated to `commands.1.log`
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+/// Rotated files kept besides the current one
+const KEPT_LOGS: usize = 3;
+
+static VERBOSITY: OnceCell<CommandLog> = OnceCell::new();
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+/// Keeps lines from commands run on different worker threads from interleaving
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set how much is logged, once at startup from the config
+pub fn init(verbosity: CommandLog) {
+    let _ = VERBOSITY.set(verbosity);
+}
+
+fn verbosity() -> CommandLog {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+/// Turn on `--dry-run` mode, in which editors and the launcher record what they
+/// would do instead of changing anything
+pub fn set_dry_run(enabled: bool) {
+    DRY_RUN.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// Record an action skipped because of `--dry-run`. Written whatever `command_log`
+/// says, since seeing these is the point of a dry run.
+pub fn log_dry_run(action: &str) {
+    let _ = append(&format!("[{}] DRY RUN: would {}\n", timestamp(), action));
+}
+
+/// Folder the command log is written to, i.e. `%LOCALAPPDATA%\TMWT\logs`
+pub fn logs_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("TMWT").join("logs"))
+}
+
+/// Run `command` to completion like `Command::output`, logging it with its result
+pub fn run_logged(command: &mut Command) -> io::Result<Output> {
+    let result = command.output();
+    let outcome = match &result {
+        Ok(output) => match output.status.code() {
+            Some(code) => format!("exit code {}", code),
+            None => "terminated".to_string(),
+        },
+        Err(e) => format!("failed to start: {}", e),
+    };
+    let output = result.as_ref().ok().map(|output| {
+        format!(
+            "{}{}",
+            captured("stdout", &output.stdout),
+            captured("stderr", &output.stderr)
+        )
+    });
+    write_entry(&describe(command), &outcome, output.as_deref());
+    result
+}
+
+/// Start `command` without waiting for it, like `Command::spawn`, logging that it was started
+pub fn spawn_logged(command: &mut Command) -> io::Result<Child> {
+    let result = command.spawn();
+    let outcome = match &result {
+        Ok(child) => format!("started (pid {})", child.id()),
+        Err(e) => format!("failed to start: {}", e),
+    };
+    write_entry(&describe(command), &outcome, None);
+    result
+}
+
+/// Log a program started through `ShellExecuteW`, which doesn't go through `Command`
+pub fn log_shell_execute(verb: &str, file: &str, parameters: &str, succeeded: bool) {
+    let command = if parameters.is_empty() {
+        file.to_string()
+    } else {
+        format!("{} {}", file, parameters)
+    };
+    let outcome = if succeeded { format!("started ({})", verb) } else { format!("failed to start ({})", verb) };
+    write_entry(&command, &outcome, None);
+}
+
+/// Program and arguments the way they would be typed, quoting arguments with spaces
+fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| {
+            let part = part.to_string_lossy();
+            if part.contains(' ') {
+                format!("\"{}\"", part)
+            } else {
+                part.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn captured(name: &str, bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+    let indented: Vec<String> = text.lines().map(|line| format!("    {}", line)).collect();
+    format!("  {}:\n{}\n", name, indented.join("\n"))
+}
+
+fn write_entry(command: &str, outcome: &str, output: Option<&str>) {
+    let verbosity = verbosity();
+    if verbosity == CommandLog::Off {
+        return;
+    }
+    let entry = match verbosity {
+        CommandLog::Full => format!("[{}] {} -> {}\n{}", timestamp(), command, outcome, output.unwrap_or_default()),
+        _ => {
+            let program = command.split_whitespace().next().unwrap_or_default();
+            format!("[{}] {} -> {}\n", timestamp(), program, outcome)
+        }
+    };
+    // Logging must never get in the way of the command itself
+    let _ = append(&entry);
+}
+
+fn append(entry: &str) -> io::Result<()> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let dir = logs_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no local app data folder"))?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("commands.log");
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() >= MAX_LOG_BYTES) {
+        rotate(&dir)?;
+    }
+    OpenOptions::new().create(true).append(true).open(&path)?.write_all(entry.as_bytes())
+}
+
+/// Shift `commands.log` to `commands.1.log`, `commands.1.log` to `commands.2.log` and so on,
+/// dropping the oldest
+fn rotate(dir: &std::path::Path) -> io::Result<()> {
+    let name = |index: usize| {
+        if index == 0 {
+            dir.join("commands.log")
+        } else {
+            dir.join(format!("commands.{}.log", index))
+        }
+    };
+    let _ = fs::remove_file(name(KEPT_LOGS));
+    for index in (0..KEPT_LOGS).rev() {
+        if name(index).exists() {
+            fs::rename(name(index), name(index + 1))?;
+        }
+    }
+    Ok(())
+}
+
+/// Seconds since the Unix epoch, which keeps entries sortable without a date library
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,165 @@
+use crate::settings_editor::{EditorType, SettingEditor, SettingOption, SettingValue, ValueFormat};
+use anyhow::{Context, Result};
+use windows::Win32::UI::WindowsAndMessaging::{
+    SystemParametersInfoW, SPIF_SENDWININICHANGE, SPIF_UPDATEINIFILE, SPI_GETKEYBOARDDELAY, SPI_GETKEYBOARDSPEED,
+    SPI_GETMOUSESPEED, SPI_SETKEYBOARDDELAY, SPI_SETKEYBOARDSPEED, SPI_SETMOUSESPEED,
+};
+
+/// Mouse pointer speed, 1 (slowest) to 20 (fastest)
+#[derive(Debug, Clone, Default)]
+pub struct MouseSpeedEditor;
+
+impl MouseSpeedEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SettingEditor for MouseSpeedEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let mut speed = 0u32;
+        unsafe { SystemParametersInfoW(SPI_GETMOUSESPEED, 0, Some(&mut speed as *mut u32 as *mut _), Default::default()) }
+            .context("Failed to read mouse speed")?;
+        Ok(SettingValue::Float(speed as f64))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Float(speed) = value {
+            // The slider's fine steps produce fractions, but Windows only takes whole speeds
+            let speed = speed.round().clamp(1.0, 20.0) as usize;
+            // SPI_SETMOUSESPEED takes the speed itself in place of a pointer
+            unsafe {
+                SystemParametersInfoW(
+                    SPI_SETMOUSESPEED,
+                    0,
+                    Some(speed as *mut _),
+                    SPIF_UPDATEINIFILE | SPIF_SENDWININICHANGE,
+                )
+            }
+            .context("Failed to set mouse speed")?;
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for mouse speed")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Float(speed) = value {
+            Ok((1.0..=20.0).contains(&speed.round()))
+        } else {
+            Ok(false)
+        }
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Slider { min: 1.0, max: 20.0, step: 1.0 }
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        Some(ValueFormat::new(0, ""))
+    }
+}
+
+/// Which half of the keyboard repeat settings a `KeyboardRepeatEditor` edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardRepeat {
+    /// Wait before a held key starts repeating, 0 (about 250ms) to 3 (about 1s)
+    Delay,
+    /// Repetitions per second once repeating, 0 (about 2.5) to 31 (about 30)
+    Speed,
+}
+
+/// Keyboard repeat delay or rate. Each is its own editor since an editor maps to one value.
+#[derive(Debug, Clone)]
+pub struct KeyboardRepeatEditor {
+    setting: KeyboardRepeat,
+}
+
+impl KeyboardRepeatEditor {
+    pub fn new(setting: KeyboardRepeat) -> Self {
+        Self { setting }
+    }
+    
+    fn max(&self) -> u32 {
+        match self.setting {
+            KeyboardRepeat::Delay => 3,
+            KeyboardRepeat::Speed => 31,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self.setting {
+            KeyboardRepeat::Delay => "keyboard repeat delay",
+            KeyboardRepeat::Speed => "keyboard repeat rate",
+        }
+    }
+}
+
+impl SettingEditor for KeyboardRepeatEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let action = match self.setting {
+            KeyboardRepeat::Delay => SPI_GETKEYBOARDDELAY,
+            KeyboardRepeat::Speed => SPI_GETKEYBOARDSPEED,
+        };
+        let mut level = 0u32;
+        unsafe { SystemParametersInfoW(action, 0, Some(&mut level as *mut u32 as *mut _), Default::default()) }
+            .with_context(|| format!("Failed to read {}", self.name()))?;
+        Ok(SettingValue::Float(level as f64))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Float(level) = value {
+            let action = match self.setting {
+                KeyboardRepeat::Delay => SPI_SETKEYBOARDDELAY,
+                KeyboardRepeat::Speed => SPI_SETKEYBOARDSPEED,
+            };
+            let level = level.round().clamp(0.0, self.max() as f64) as u32;
+            // Both SPI_SET actions take the level in uiParam
+            unsafe { SystemParametersInfoW(action, level, None, SPIF_UPDATEINIFILE | SPIF_SENDWININICHANGE) }
+                .with_context(|| format!("Failed to set {}", self.name()))?;
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for {}", self.name())
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Float(level) = value {
+            Ok((0.0..=self.max() as f64).contains(&level.round()))
+        } else {
+            Ok(false)
+        }
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Slider { min: 0.0, max: self.max() as f64, step: 1.0 }
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        Some(ValueFormat::new(0, ""))
+    }
+}
@@ -0,0 +1,587 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::HashMap, fmt, fs, path::PathBuf};
+use crate::config::app_dir;
+
+/// Everything a key can be bound to in `keys.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Search,
+    SwitchFocus,
+    MoveUp,
+    MoveDown,
+    JumpToTop,
+    JumpToBottom,
+    PageUp,
+    PageDown,
+    FocusCategories,
+    FocusItems,
+    Open,
+    Edit,
+    Favorite,
+    Undo,
+    Accept,
+    Select,
+    Save,
+    SaveText,
+    Cancel,
+    Help,
+    CycleTheme,
+    ToggleDetails,
+    RelaunchElevated,
+    Export,
+    Import,
+    Profiles,
+    CaptureProfile,
+    ToggleStaging,
+    ApplyStaged,
+    Refresh,
+    WidenCategories,
+    NarrowCategories,
+    ToggleDensity,
+    Copy,
+    Rename,
+}
+
+/// Actions looked up in each input mode, in priority order
+pub const NORMAL_ACTIONS: &[Action] = &[
+    Action::Quit,
+    Action::Search,
+    Action::Help,
+    Action::CycleTheme,
+    Action::ToggleDetails,
+    Action::RelaunchElevated,
+    Action::Export,
+    Action::Import,
+    Action::Profiles,
+    Action::ToggleStaging,
+    Action::ApplyStaged,
+    Action::Refresh,
+    Action::ToggleDensity,
+    Action::SwitchFocus,
+    Action::MoveDown,
+    Action::MoveUp,
+    Action::JumpToTop,
+    Action::JumpToBottom,
+    Action::PageUp,
+    Action::PageDown,
+    Action::Undo,
+    Action::Favorite,
+    Action::Copy,
+    Action::Rename,
+    Action::FocusCategories,
+    Action::FocusItems,
+    Action::WidenCategories,
+    Action::NarrowCategories,
+    Action::Open,
+    Action::Edit,
+    Action::Cancel,
+];
+pub const SEARCH_ACTIONS: &[Action] = &[Action::Accept, Action::Cancel];
+pub const EDIT_ACTIONS: &[Action] = &[
+    Action::Cancel,
+    Action::Save,
+    Action::Select,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Quit,
+];
+pub const TEXT_EDIT_ACTIONS: &[Action] = &[Action::Cancel, Action::SaveText];
+pub const HELP_ACTIONS: &[Action] = &[Action::Help, Action::Cancel];
+pub const BATCH_ACTIONS: &[Action] = &[
+    Action::Cancel,
+    Action::Select,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::CaptureProfile,
+];
+
+/// Sections of the help overlay
+pub const HELP_SECTIONS: &[(&str, &[Action])] = &[
+    ("Normal", NORMAL_ACTIONS),
+    ("Search", SEARCH_ACTIONS),
+    ("Edit", EDIT_ACTIONS),
+    ("Text Input", TEXT_EDIT_ACTIONS),
+];
+
+/// Normal-mode keys that can't be rebound, listed under Normal in the help overlay
+pub const FIXED_NORMAL_KEYS: &[(&str, &str)] = &[("1-9", "Jump to a numbered category")];
+
+/// Search keys that can't be rebound, listed under Search in the help overlay
+pub const FIXED_SEARCH_KEYS: &[(&str, &str)] = &[("↑/↓", "Recall earlier searches")];
+
+/// Editor keys that can't be rebound, listed under Edit in the help overlay
+pub const FIXED_EDIT_KEYS: &[(&str, &str)] = &[
+    ("0-9", "Type a number"),
+    ("Backspace", "Delete a digit"),
+    ("PgUp/PgDn", "Change a number by 10"),
+    ("←/→", "Adjust a slider (Shift for fine steps)"),
+    ("p", "Preview display modes live while browsing resolutions"),
+];
+
+impl Action {
+    pub const ALL: [Action; 36] = [
+        Action::Quit,
+        Action::Search,
+        Action::SwitchFocus,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::JumpToTop,
+        Action::JumpToBottom,
+        Action::PageUp,
+        Action::PageDown,
+        Action::FocusCategories,
+        Action::FocusItems,
+        Action::Open,
+        Action::Edit,
+        Action::Favorite,
+        Action::Undo,
+        Action::Accept,
+        Action::Select,
+        Action::Save,
+        Action::SaveText,
+        Action::Cancel,
+        Action::Help,
+        Action::CycleTheme,
+        Action::ToggleDetails,
+        Action::RelaunchElevated,
+        Action::Export,
+        Action::Import,
+        Action::Profiles,
+        Action::CaptureProfile,
+        Action::ToggleStaging,
+        Action::ApplyStaged,
+        Action::Refresh,
+        Action::WidenCategories,
+        Action::NarrowCategories,
+        Action::ToggleDensity,
+        Action::Copy,
+        Action::Rename,
+    ];
+
+    /// Name used for the action in `keys.toml`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Search => "search",
+            Action::SwitchFocus => "switch_focus",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::JumpToTop => "jump_to_top",
+            Action::JumpToBottom => "jump_to_bottom",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::FocusCategories => "focus_categories",
+            Action::FocusItems => "focus_items",
+            Action::Open => "open",
+            Action::Edit => "edit",
+            Action::Favorite => "favorite",
+            Action::Undo => "undo",
+            Action::Accept => "accept_search",
+            Action::Select => "select",
+            Action::Save => "save",
+            Action::SaveText => "save_text",
+            Action::Cancel => "cancel",
+            Action::Help => "help",
+            Action::CycleTheme => "cycle_theme",
+            Action::ToggleDetails => "toggle_details",
+            Action::RelaunchElevated => "relaunch_elevated",
+            Action::Export => "export",
+            Action::Import => "import",
+            Action::Profiles => "profiles",
+            Action::CaptureProfile => "capture_profile",
+            Action::ToggleStaging => "toggle_staging",
+            Action::ApplyStaged => "apply_staged",
+            Action::Refresh => "refresh",
+            Action::WidenCategories => "widen_categories",
+            Action::NarrowCategories => "narrow_categories",
+            Action::ToggleDensity => "toggle_density",
+            Action::Copy => "copy",
+            Action::Rename => "rename",
+        }
+    }
+
+    /// Short description shown in the status bar and help overlay
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Search => "Search",
+            Action::SwitchFocus => "Switch",
+            Action::MoveUp => "Move up",
+            Action::MoveDown => "Move down",
+            Action::JumpToTop => "Jump to top",
+            Action::JumpToBottom => "Jump to bottom",
+            Action::PageUp => "Page up",
+            Action::PageDown => "Page down",
+            Action::FocusCategories => "Focus categories",
+            Action::FocusItems => "Focus items",
+            Action::Open => "Open/Edit",
+            Action::Edit => "Edit",
+            Action::Favorite => "Favorite",
+            Action::Undo => "Undo",
+            Action::Accept => "Confirm",
+            Action::Select => "Select",
+            Action::Save => "Save",
+            Action::SaveText => "Save",
+            Action::Cancel => "Cancel, or clear the search results",
+            Action::Help => "Help",
+            Action::CycleTheme => "Theme",
+            Action::ToggleDetails => "Details",
+            Action::RelaunchElevated => "Relaunch as admin",
+            Action::Export => "Export snapshot",
+            Action::Import => "Import snapshot",
+            Action::Profiles => "Settings profiles",
+            Action::CaptureProfile => "Capture current settings as a profile",
+            Action::ToggleStaging => "Stage changes instead of saving",
+            Action::ApplyStaged => "Apply staged changes",
+            Action::Refresh => "Re-read current values",
+            Action::WidenCategories => "Widen categories pane",
+            Action::NarrowCategories => "Narrow categories pane",
+            Action::ToggleDensity => "Switch between compact and detailed list",
+            Action::Copy => "Copy current value to the clipboard",
+            Action::Rename => "Give the selected item a name of your own",
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Search => &["/"],
+            Action::SwitchFocus => &["Tab"],
+            Action::MoveUp => &["Up", "k"],
+            Action::MoveDown => &["Down", "j"],
+            Action::JumpToTop => &["g", "Home"],
+            Action::JumpToBottom => &["G", "End"],
+            Action::PageUp => &["PageUp"],
+            Action::PageDown => &["PageDown"],
+            Action::FocusCategories => &["h"],
+            Action::FocusItems => &["l"],
+            Action::Open => &["Enter"],
+            Action::Edit => &["e"],
+            Action::Favorite => &["f"],
+            Action::Undo => &["u"],
+            Action::Accept => &["Enter"],
+            Action::Select => &["Enter", "Space"],
+            Action::Save => &["s", "S"],
+            // Text editors take plain characters as input, so they save with a modifier
+            Action::SaveText => &["Ctrl+s"],
+            Action::Cancel => &["Esc"],
+            Action::Help => &["?"],
+            Action::CycleTheme => &["t"],
+            Action::ToggleDetails => &["d"],
+            Action::RelaunchElevated => &["A"],
+            Action::Export => &["x"],
+            Action::Import => &["i"],
+            Action::Profiles => &["p"],
+            Action::CaptureProfile => &["c"],
+            Action::ToggleStaging => &["b"],
+            Action::ApplyStaged => &["a"],
+            Action::Refresh => &["r"],
+            Action::WidenCategories => &["Ctrl+Right"],
+            Action::NarrowCategories => &["Ctrl+Left"],
+            Action::ToggleDensity => &["v"],
+            Action::Copy => &["y"],
+            Action::Rename => &["R"],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parse keys like `q`, `G`, `Enter`, `PageDown`, `Ctrl+s` or `Shift+Tab`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = text.split('+').collect();
+        // "+" on its own (or as in "Ctrl++") is the plus key rather than a separator
+        let key = if text.ends_with("++") || text == "+" {
+            parts.truncate(parts.len().saturating_sub(2));
+            "+"
+        } else {
+            parts.pop()?
+        };
+
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                other => {
+                    let number = other.strip_prefix('f')?.parse::<u8>().ok()?;
+                    if !(1..=24).contains(&number) {
+                        return None;
+                    }
+                    KeyCode::F(number)
+                }
+            },
+        };
+
+        // Shifted characters arrive as the shifted character itself
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+
+        Some(Self { code, modifiers })
+    }
+
+    /// Whether `key` is this binding with exactly its modifiers, so e.g. `Left` doesn't
+    /// also fire on Ctrl+Left
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // Shifted characters and Shift+Tab already carry the shift in their code
+        let mut pressed = key.modifiers;
+        if matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            pressed.remove(KeyModifiers::SHIFT);
+        }
+        if pressed != self.modifiers {
+            return false;
+        }
+        match (self.code, key.code) {
+            // Terminals disagree on the case of Ctrl/Alt letters
+            (KeyCode::Char(bound), KeyCode::Char(pressed)) if !self.modifiers.is_empty() => {
+                bound.eq_ignore_ascii_case(&pressed)
+            }
+            (bound, pressed) => bound == pressed,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if !self.modifiers.is_empty() => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Keys for each `Action`, read from `%APPDATA%\TMWT\keys.toml`
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .filter_map(|key| KeyBinding::parse(key))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyBindings {
+    pub fn path() -> Option<PathBuf> {
+        app_dir().map(|dir| dir.join("keys.toml"))
+    }
+
+    /// Load the user's bindings on top of the defaults.
+    /// Problems with the file are returned as warnings and the affected actions keep their default keys.
+    pub fn load() -> (Self, Vec<String>) {
+        let mut key_bindings = Self::default();
+        let mut warnings = Vec::new();
+
+        let Some(contents) = Self::path().and_then(|path| fs::read_to_string(path).ok()) else {
+            return (key_bindings, warnings);
+        };
+        let table: toml::Table = match contents.parse() {
+            Ok(table) => table,
+            Err(e) => {
+                warnings.push(format!("keys.toml is not valid TOML, using default keys: {}", e.message()));
+                return (key_bindings, warnings);
+            }
+        };
+
+        for (name, value) in table {
+            let Some(action) = Action::from_name(&name) else {
+                warnings.push(format!("keys.toml: unknown action '{}'", name));
+                continue;
+            };
+
+            // Accept either a single key or a list of keys
+            let keys: Vec<&toml::Value> = match &value {
+                toml::Value::Array(keys) => keys.iter().collect(),
+                key => vec![key],
+            };
+            let parsed: Option<Vec<KeyBinding>> = keys
+                .iter()
+                .map(|key| key.as_str().and_then(KeyBinding::parse))
+                .collect();
+            match parsed {
+                Some(parsed) if !parsed.is_empty() => {
+                    key_bindings.bindings.insert(action, parsed);
+                }
+                _ => warnings.push(format!(
+                    "keys.toml: invalid keys for '{}', using the default",
+                    name
+                )),
+            }
+        }
+
+        (key_bindings, warnings)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// All keys bound to `action`, e.g. "↓/j"
+    pub fn describe_keys(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Status bar hint like "[Enter] Open/Edit  [q] Quit", using the first key of each action
+    pub fn hint(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .filter_map(|action| {
+                self.keys(*action)
+                    .first()
+                    .map(|key| format!("[{}] {}", key, action.description()))
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    /// The first of `actions` bound to `key`, so callers only consider actions valid in their mode
+    pub fn action_for(&self, key: &KeyEvent, actions: &[Action]) -> Option<Action> {
+        actions
+            .iter()
+            .copied()
+            .find(|action| self.keys(*action).iter().any(|binding| binding.matches(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn binding(text: &str) -> KeyBinding {
+        KeyBinding::parse(text).unwrap()
+    }
+
+    #[test]
+    fn unmodified_bindings_ignore_ctrl_and_alt() {
+        assert!(binding("Left").matches(&key(KeyCode::Left, KeyModifiers::NONE)));
+        assert!(!binding("Left").matches(&key(KeyCode::Left, KeyModifiers::CONTROL)));
+        assert!(!binding("q").matches(&key(KeyCode::Char('q'), KeyModifiers::ALT)));
+    }
+
+    #[test]
+    fn shifted_characters_match_their_binding() {
+        assert!(binding("G").matches(&key(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+        assert!(binding("?").matches(&key(KeyCode::Char('?'), KeyModifiers::SHIFT)));
+        assert!(binding("Shift+Tab").matches(&key(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert!(!binding("g").matches(&key(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+    }
+
+    #[test]
+    fn modified_bindings_need_their_modifiers() {
+        assert!(binding("Ctrl+s").matches(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert!(!binding("Ctrl+s").matches(&key(KeyCode::Char('s'), KeyModifiers::NONE)));
+        assert!(!binding("Ctrl+Right").matches(&key(KeyCode::Right, KeyModifiers::CONTROL | KeyModifiers::ALT)));
+    }
+
+    #[test]
+    fn shifted_arrows_stay_free_for_fine_steps() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.action_for(&key(KeyCode::Right, KeyModifiers::SHIFT), NORMAL_ACTIONS), None);
+    }
+
+    #[test]
+    fn resizing_does_not_depend_on_action_order() {
+        let mut bindings = KeyBindings::default();
+        bindings.bindings.insert(Action::FocusCategories, vec![binding("Left")]);
+        bindings.bindings.insert(Action::FocusItems, vec![binding("Right")]);
+        assert_eq!(
+            bindings.action_for(&key(KeyCode::Left, KeyModifiers::CONTROL), NORMAL_ACTIONS),
+            Some(Action::NarrowCategories)
+        );
+        assert_eq!(
+            bindings.action_for(&key(KeyCode::Right, KeyModifiers::NONE), NORMAL_ACTIONS),
+            Some(Action::FocusItems)
+        );
+    }
+}
@@ -1,196 +1,366 @@
-use crate::settings::{LaunchType, SettingsItem};
+use crate::{
+    exec,
+    settings::{LaunchType, SettingsItem},
+};
 use anyhow::{Context, Result};
 use std::process::Command;
 use windows::{
     core::PCWSTR,
     Win32::{
-        Foundation::HWND,
+        Foundation::{CloseHandle, HANDLE, HWND},
+        Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY},
+        System::Threading::{GetCurrentProcess, OpenProcessToken},
         UI::Shell::ShellExecuteW,
         UI::WindowsAndMessaging::SW_SHOWNORMAL,
     },
 };
 
-pub fn launch_setting(item: &SettingsItem) -> Result<()> {
-    match &item.launch_command {
+/// Whether this process runs with an elevated (administrator) token
+pub fn is_elevated() -> bool {
+    unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut returned = 0u32;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut _ as *mut _),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(token);
+        
+        result.is_ok() && elevation.TokenIsElevated != 0
+    }
+}
+
+/// Start an elevated copy of TMWT with the same arguments. Windows shows the UAC
+/// prompt and opens the new instance in its own console, so the caller should quit
+/// and restore its terminal before calling this.
+pub fn relaunch_elevated() -> Result<()> {
+    let exe = std::env::current_exe().context("Could not locate the TMWT executable")?;
+    let args = std::env::args()
+        .skip(1)
+        .map(|arg| format!("\"{}\"", arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let dir = std::env::current_dir().ok();
+    
+    unsafe {
+        let exe_wide = to_wide_string(&exe.to_string_lossy());
+        let args_wide = to_wide_string(&args);
+        let dir_wide = dir.map(|dir| to_wide_string(&dir.to_string_lossy()));
+        let verb_wide = to_wide_string("runas");
+        
+        let result = ShellExecuteW(
+            HWND(0),
+            PCWSTR(verb_wide.as_ptr()),
+            PCWSTR(exe_wide.as_ptr()),
+            PCWSTR(args_wide.as_ptr()),
+            dir_wide.as_ref().map_or(PCWSTR::null(), |dir| PCWSTR(dir.as_ptr())),
+            SW_SHOWNORMAL,
+        );
+        
+        exec::log_shell_execute("runas", &exe.to_string_lossy(), &args, result.0 as usize > 32);
+        if result.0 as usize == SE_ERR_ACCESSDENIED {
+            anyhow::bail!("Elevation cancelled, TMWT was not restarted as administrator");
+        }
+        if result.0 as usize <= 32 {
+            anyhow::bail!("Failed to relaunch TMWT as administrator: {}", shell_execute_error(result.0 as usize));
+        }
+    }
+    
+    Ok(())
+}
+
+/// How a launch that didn't fail ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchOutcome {
+    Launched,
+    /// The user turned down the UAC prompt for an admin item, which isn't an error
+    ElevationCancelled,
+}
+
+pub fn launch_setting(item: &SettingsItem) -> Result<LaunchOutcome> {
+    if exec::is_dry_run() {
+        exec::log_dry_run(&format!("open {} via {:?}", item.name, item.launch_command));
+        return Ok(LaunchOutcome::Launched);
+    }
+    
+    let result = match &item.launch_command {
         LaunchType::MsSettings(uri) => launch_ms_settings(uri, item.requires_admin),
         LaunchType::ControlPanel(cpl) => launch_control_panel(cpl, item.requires_admin),
         LaunchType::RunDll32(cmd) => launch_rundll32(cmd, item.requires_admin),
         LaunchType::PowerShell(cmd) => launch_powershell(cmd, item.requires_admin),
         LaunchType::Command(cmd) => launch_command(cmd, item.requires_admin),
+        LaunchType::WindowsTerminal { profile, command } => {
+            launch_windows_terminal(profile.as_deref(), command.as_deref(), item.requires_admin)
+        }
+        LaunchType::Url(url) => launch_url(url),
+    };
+    result.with_context(|| format!("Could not open {} ({})", item.name, command_line(&item.launch_command)))
+}
+
+/// The command line a launch resolves to, so a broken applet or URI can be spotted in errors
+pub fn command_line(launch: &LaunchType) -> String {
+    match launch {
+        LaunchType::MsSettings(uri) => format!("start ms-settings:{}", uri),
+        LaunchType::ControlPanel(cpl) => format!("control {}", cpl),
+        LaunchType::RunDll32(cmd) => format!("rundll32.exe {}", cmd),
+        LaunchType::PowerShell(cmd) => format!("powershell -Command \"{}\"", cmd),
+        LaunchType::Command(cmd) => cmd.clone(),
+        LaunchType::WindowsTerminal { profile, command } => {
+            let args = wt_arguments(profile.as_deref(), command.as_deref());
+            format!("wt.exe {}", args.join(" ")).trim_end().to_string()
+        }
+        LaunchType::Url(url) => format!("open {}", url),
+    }
+}
+
+/// Explain a `ShellExecuteW` result of 32 or below, which is an error code rather than a handle
+fn shell_execute_error(code: usize) -> String {
+    let reason = match code {
+        0 | 8 => "out of memory",
+        2 => "file not found",
+        3 => "path not found",
+        5 => "access denied",
+        11 => "not a valid program",
+        26 => "sharing violation",
+        27 | 31 => "no application is associated with this file type",
+        28..=30 => "the application did not respond",
+        32 => "a required DLL was not found",
+        _ => return format!("error code {}", code),
+    };
+    reason.to_string()
+}
+
+/// Result of a `runas` request, which the user can turn down at the UAC prompt
+enum Elevation {
+    Started,
+    Cancelled,
+    /// `ShellExecuteW` error code, see `shell_execute_error`
+    Failed(usize),
+}
+
+/// `SE_ERR_ACCESSDENIED`, which is also what `ShellExecuteW` returns when the UAC prompt is declined
+const SE_ERR_ACCESSDENIED: usize = 5;
+
+/// Start `file` with `parameters` through the `runas` verb, so Windows shows the UAC prompt
+fn run_elevated(file: &str, parameters: &str) -> Elevation {
+    let code = unsafe {
+        let file_wide = to_wide_string(file);
+        let params_wide = to_wide_string(parameters);
+        let verb_wide = to_wide_string("runas");
+        
+        ShellExecuteW(
+            HWND(0),
+            PCWSTR(verb_wide.as_ptr()),
+            PCWSTR(file_wide.as_ptr()),
+            if parameters.is_empty() { PCWSTR::null() } else { PCWSTR(params_wide.as_ptr()) },
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+        .0 as usize
+    };
+    
+    exec::log_shell_execute("runas", file, parameters, code > 32);
+    match code {
+        SE_ERR_ACCESSDENIED => Elevation::Cancelled,
+        code if code <= 32 => Elevation::Failed(code),
+        _ => Elevation::Started,
     }
 }
 
-fn launch_ms_settings(uri: &str, requires_admin: bool) -> Result<()> {
+/// Turn an elevated start into a launch outcome, naming `what` failed to start
+fn elevated_outcome(elevation: Elevation, what: &str) -> Result<LaunchOutcome> {
+    match elevation {
+        Elevation::Started => Ok(LaunchOutcome::Launched),
+        Elevation::Cancelled => Ok(LaunchOutcome::ElevationCancelled),
+        Elevation::Failed(code) => {
+            anyhow::bail!("Failed to launch {} with admin privileges: {}", what, shell_execute_error(code))
+        }
+    }
+}
+
+fn launch_ms_settings(uri: &str, requires_admin: bool) -> Result<LaunchOutcome> {
     let full_uri = format!("ms-settings:{}", uri);
     
     if requires_admin {
-        // Use ShellExecute for elevation
-        unsafe {
-            let uri_wide = to_wide_string(&full_uri);
-            let verb_wide = to_wide_string("runas");
-            
-            let result = ShellExecuteW(
-                HWND(0),
-                PCWSTR(verb_wide.as_ptr()),
-                PCWSTR(uri_wide.as_ptr()),
-                PCWSTR::null(),
-                PCWSTR::null(),
-                SW_SHOWNORMAL,
-            );
-            
-            if result.0 as usize <= 32 {
-                anyhow::bail!("Failed to launch settings with admin privileges");
-            }
-        }
-    } else {
-        Command::new("cmd")
-            .args(&["/c", "start", &full_uri])
-            .spawn()
-            .context("Failed to launch Settings app")?;
+        return elevated_outcome(run_elevated(&full_uri, ""), "settings");
     }
+    exec::spawn_logged(Command::new("cmd").args(["/c", "start", &full_uri]))
+        .context("Failed to launch Settings app")?;
     
-    Ok(())
+    Ok(LaunchOutcome::Launched)
 }
 
-fn launch_control_panel(cpl: &str, requires_admin: bool) -> Result<()> {
+fn launch_control_panel(cpl: &str, requires_admin: bool) -> Result<LaunchOutcome> {
     if requires_admin {
-        // Use ShellExecute for elevation
-        unsafe {
-            let control_wide = to_wide_string("control.exe");
-            let cpl_wide = to_wide_string(cpl);
-            let verb_wide = to_wide_string("runas");
-            
-            let result = ShellExecuteW(
-                HWND(0),
-                PCWSTR(verb_wide.as_ptr()),
-                PCWSTR(control_wide.as_ptr()),
-                PCWSTR(cpl_wide.as_ptr()),
-                PCWSTR::null(),
-                SW_SHOWNORMAL,
-            );
-            
-            if result.0 as usize <= 32 {
-                anyhow::bail!("Failed to launch Control Panel with admin privileges");
-            }
-        }
-    } else {
-        Command::new("control")
-            .arg(cpl)
-            .spawn()
-            .context("Failed to launch Control Panel")?;
+        return elevated_outcome(run_elevated("control.exe", cpl), "Control Panel");
     }
+    exec::spawn_logged(Command::new("control").arg(cpl))
+        .context("Failed to launch Control Panel")?;
     
-    Ok(())
+    Ok(LaunchOutcome::Launched)
 }
 
-fn launch_rundll32(cmd: &str, requires_admin: bool) -> Result<()> {
+fn launch_rundll32(cmd: &str, requires_admin: bool) -> Result<LaunchOutcome> {
     let parts: Vec<&str> = cmd.splitn(2, ' ').collect();
     if parts.is_empty() {
         anyhow::bail!("Invalid rundll32 command");
     }
     
     if requires_admin {
-        // Use ShellExecute for elevation
-        unsafe {
-            let rundll_wide = to_wide_string("rundll32.exe");
-            let cmd_wide = to_wide_string(cmd);
-            let verb_wide = to_wide_string("runas");
-            
-            let result = ShellExecuteW(
-                HWND(0),
-                PCWSTR(verb_wide.as_ptr()),
-                PCWSTR(rundll_wide.as_ptr()),
-                PCWSTR(cmd_wide.as_ptr()),
-                PCWSTR::null(),
-                SW_SHOWNORMAL,
-            );
-            
-            if result.0 as usize <= 32 {
-                anyhow::bail!("Failed to launch rundll32 with admin privileges");
-            }
-        }
-    } else {
-        let mut command = Command::new("rundll32.exe");
-        command.args(parts);
-        command.spawn()
-            .context("Failed to launch rundll32 command")?;
+        return elevated_outcome(run_elevated("rundll32.exe", cmd), "rundll32");
     }
+    exec::spawn_logged(Command::new("rundll32.exe").args(parts))
+        .context("Failed to launch rundll32 command")?;
     
-    Ok(())
+    Ok(LaunchOutcome::Launched)
 }
 
-fn launch_powershell(cmd: &str, requires_admin: bool) -> Result<()> {
+fn launch_powershell(cmd: &str, requires_admin: bool) -> Result<LaunchOutcome> {
     if requires_admin {
-        // Use ShellExecute for elevation
-        unsafe {
-            let ps_wide = to_wide_string("powershell.exe");
-            let args_wide = to_wide_string(&format!("-Command \"{}\"", cmd));
-            let verb_wide = to_wide_string("runas");
-            
-            let result = ShellExecuteW(
-                HWND(0),
-                PCWSTR(verb_wide.as_ptr()),
-                PCWSTR(ps_wide.as_ptr()),
-                PCWSTR(args_wide.as_ptr()),
-                PCWSTR::null(),
-                SW_SHOWNORMAL,
-            );
-            
-            if result.0 as usize <= 32 {
-                anyhow::bail!("Failed to launch PowerShell with admin privileges");
-            }
-        }
-    } else {
-        Command::new("powershell")
-            .args(&["-Command", cmd])
-            .spawn()
-            .context("Failed to launch PowerShell command")?;
+        let ps_args = format!("-Command \"{}\"", cmd);
+        return elevated_outcome(run_elevated("powershell.exe", &ps_args), "PowerShell");
     }
+    exec::spawn_logged(Command::new("powershell").args(["-Command", cmd]))
+        .context("Failed to launch PowerShell command")?;
     
-    Ok(())
+    Ok(LaunchOutcome::Launched)
 }
 
-fn launch_command(cmd: &str, requires_admin: bool) -> Result<()> {
+fn launch_command(cmd: &str, requires_admin: bool) -> Result<LaunchOutcome> {
     let parts: Vec<&str> = cmd.split_whitespace().collect();
     if parts.is_empty() {
         anyhow::bail!("Invalid command");
     }
     
     if requires_admin {
-        // Use ShellExecute for elevation
-        unsafe {
-            let exe_wide = to_wide_string(parts[0]);
-            let args = if parts.len() > 1 {
-                parts[1..].join(" ")
-            } else {
-                String::new()
-            };
-            let args_wide = to_wide_string(&args);
-            let verb_wide = to_wide_string("runas");
-            
-            let result = ShellExecuteW(
-                HWND(0),
-                PCWSTR(verb_wide.as_ptr()),
-                PCWSTR(exe_wide.as_ptr()),
-                if args.is_empty() { PCWSTR::null() } else { PCWSTR(args_wide.as_ptr()) },
-                PCWSTR::null(),
-                SW_SHOWNORMAL,
-            );
-            
-            if result.0 as usize <= 32 {
-                anyhow::bail!("Failed to launch command with admin privileges");
-            }
+        let args = parts[1..].join(" ");
+        return elevated_outcome(run_elevated(parts[0], &args), "command");
+    }
+    exec::spawn_logged(Command::new(parts[0]).args(&parts[1..]))
+        .context("Failed to launch command")?;
+    
+    Ok(LaunchOutcome::Launched)
+}
+
+/// Arguments for `wt.exe`: `-p <profile>` when a profile is named, then the command to run in it
+fn wt_arguments(profile: Option<&str>, command: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(profile) = profile {
+        args.push("-p".to_string());
+        args.push(profile.to_string());
+    }
+    if let Some(command) = command {
+        args.extend(command.split_whitespace().map(str::to_string));
+    }
+    args
+}
+
+/// `ERROR_FILE_NOT_FOUND`, returned by `ShellExecuteW` when Windows Terminal isn't installed
+const SE_ERR_FNF: usize = 2;
+
+fn launch_windows_terminal(profile: Option<&str>, command: Option<&str>, requires_admin: bool) -> Result<LaunchOutcome> {
+    let args = wt_arguments(profile, command);
+    
+    if requires_admin {
+        let wt_args = args
+            .iter()
+            .map(|arg| if arg.contains(' ') { format!("\"{}\"", arg) } else { arg.clone() })
+            .collect::<Vec<_>>()
+            .join(" ");
+        return match run_elevated("wt.exe", &wt_args) {
+            Elevation::Failed(SE_ERR_FNF) => launch_conhost(profile, command, requires_admin),
+            elevation => elevated_outcome(elevation, "Windows Terminal"),
+        };
+    }
+    match exec::spawn_logged(Command::new("wt.exe").args(&args)) {
+        Ok(_) => Ok(LaunchOutcome::Launched),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => launch_conhost(profile, command, requires_admin),
+        Err(e) => Err(e).context("Failed to launch Windows Terminal"),
+    }
+}
+
+/// Fallback for machines without Windows Terminal. The classic console host can run
+/// the command, but has no profiles to open.
+fn launch_conhost(profile: Option<&str>, command: Option<&str>, requires_admin: bool) -> Result<LaunchOutcome> {
+    if let Some(profile) = profile {
+        anyhow::bail!(
+            "Windows Terminal (wt.exe) is not installed, so the '{}' profile cannot be opened. Install Windows Terminal from the Microsoft Store",
+            profile
+        );
+    }
+    launch_command(&format!("conhost.exe {}", command.unwrap_or("cmd.exe")), requires_admin)
+}
+
+/// Schemes `launch_url` will hand to the shell. Anything else, e.g. `file:`, a bare path
+/// or a protocol handler like `ms-msdt:`, could run a program instead of opening a page.
+const URL_SCHEMES: [&str; 4] = ["http", "https", "ms-settings", "ms-windows-store"];
+
+/// Whether `url`'s scheme, the part before the first `:`, is in `URL_SCHEMES`
+fn is_allowed_url(url: &str) -> bool {
+    url.split_once(':')
+        .is_some_and(|(scheme, _)| URL_SCHEMES.iter().any(|allowed| scheme.eq_ignore_ascii_case(allowed)))
+}
+
+fn launch_url(url: &str) -> Result<LaunchOutcome> {
+    // The shell gets exactly the string that was checked
+    let url = url.trim();
+    if !is_allowed_url(url) {
+        anyhow::bail!("Refusing to open '{}': only {} links are allowed", url, URL_SCHEMES.join(", "));
+    }
+    
+    // Opened in the user's own context even for admin items, since browsers shouldn't run elevated
+    unsafe {
+        let url_wide = to_wide_string(url);
+        let verb_wide = to_wide_string("open");
+        
+        let result = ShellExecuteW(
+            HWND(0),
+            PCWSTR(verb_wide.as_ptr()),
+            PCWSTR(url_wide.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        );
+        
+        exec::log_shell_execute("open", url, "", result.0 as usize > 32);
+        if result.0 as usize <= 32 {
+            anyhow::bail!("Failed to open link: {}", shell_execute_error(result.0 as usize));
         }
-    } else {
-        Command::new(parts[0])
-            .args(&parts[1..])
-            .spawn()
-            .context("Failed to launch command")?;
     }
     
-    Ok(())
+    Ok(LaunchOutcome::Launched)
 }
 
 fn to_wide_string(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(std::iter::once(0)).collect()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_web_and_settings_links() {
+        assert!(is_allowed_url("https://learn.microsoft.com/windows/release-health/"));
+        assert!(is_allowed_url("HTTP://example.com"));
+        assert!(is_allowed_url("ms-settings:display"));
+        assert!(is_allowed_url("ms-windows-store://pdp/?productid=9NBLGGH4NNS1"));
+    }
+
+    #[test]
+    fn refuses_other_protocol_handlers() {
+        assert!(!is_allowed_url("ms-msdt:/id PCWDiagnostic"));
+        assert!(!is_allowed_url("ms-officecmd:{}"));
+        assert!(!is_allowed_url("file:///C:/Windows/System32/cmd.exe"));
+        assert!(!is_allowed_url(r"C:\Windows\System32\cmd.exe"));
+        assert!(!is_allowed_url("https"));
+    }
+}
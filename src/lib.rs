@@ -0,0 +1,21 @@
+//! TMWT's settings catalog and editors, usable without the TUI.
+//!
+//! Every editable item in [`SETTINGS_ITEMS`] names an editor key, which
+//! [`create_editor`] turns into a [`SettingEditor`] that reads and writes the
+//! setting directly, e.g. `create_editor("master_volume")`.
+
+pub mod settings;
+pub mod settings_editor;
+pub mod network_editor;
+pub mod display_editor;
+pub mod personalization_editor;
+pub mod system_editor;
+pub mod input_editor;
+pub mod apps_editor;
+pub mod audio_editor;
+pub mod registry;
+pub mod launcher;
+pub mod exec;
+
+pub use settings::{SettingsItem, SETTINGS_ITEMS};
+pub use settings_editor::{create_editor, EditorType, SettingEditor, SettingOption, SettingValue};
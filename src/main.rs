@@ -1,51 +1,104 @@
 mod app;
-mod settings;
 mod ui;
-mod launcher;
 mod event;
 mod edit_ui;
-mod settings_editor;
-mod network_editor;
+mod config;
+mod search;
+mod theme;
+mod keybindings;
+mod worker;
+mod snapshot;
+mod profiles;
+mod clipboard;
+mod cli;
+
+// The editors live in the library, imported here so the TUI modules reach them through `crate::`
+use tmwt::{exec, launcher, network_editor, settings, settings_editor};
 
 use anyhow::Result;
 use crossterm::{
+    cursor::Show,
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    tty::IsTty,
 };
 use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use std::{io, panic, time::Duration};
-use crate::event::EventHandler;
+use std::{
+    io, panic,
+    process::ExitCode,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use crate::event::{EventHandler, InputOptions};
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     // Setup panic handler to restore terminal
     setup_panic_handler();
     
-    // Setup terminal
-    let mut terminal = setup_terminal()?;
+    // Reads still happen in a dry run, but nothing is changed or launched
+    let (dry_run, args): (Vec<String>, Vec<String>) = std::env::args().skip(1).partition(|arg| arg == "--dry-run");
+    if !dry_run.is_empty() {
+        exec::set_dry_run(true);
+    }
+    
+    // Subcommands like `apply` do their work and exit without touching the terminal
+    if let Some(code) = cli::run(&args) {
+        return Ok(code);
+    }
+    
+    // Raw mode and the alternate screen need a real console on both ends, and fail
+    // with unhelpful errors when output is redirected
+    if !io::stdout().is_tty() || !io::stdin().is_tty() {
+        eprintln!("TMWT requires an interactive terminal; run it directly in a console window without redirecting input or output");
+        return Ok(ExitCode::FAILURE);
+    }
+    
+    // Setup terminal, undoing whatever part of it succeeded if it fails
+    let mut terminal = setup_terminal().inspect_err(|_| {
+        let _ = restore_terminal();
+    })?;
     
     // Create event handler
-    let event_handler = EventHandler::new(Duration::from_millis(250));
+    let config = config::Config::load();
+    let input_options = InputOptions {
+        coalesce_key_repeats: config.coalesce_key_repeats,
+        ..InputOptions::default()
+    };
+    let event_handler = EventHandler::new(app::DEFAULT_TICK_RATE, input_options);
 
     // Create app and run
-    let app = app::App::new();
+    let app = app::App::new(event_handler.sender());
     let res = app::run_app(&mut terminal, app, event_handler);
     
     // Restore terminal
-    restore_terminal(&mut terminal)?;
+    restore_terminal()?;
     
-    if let Err(e) = res {
-        eprintln!("Error: {:?}", e);
-    }
+    let result = match res {
+        Ok(app::AppExit::Quit) => Ok(()),
+        // Only now that the terminal is back to normal can the elevated instance take over
+        Ok(app::AppExit::RelaunchElevated) => launcher::relaunch_elevated(),
+        Err(e) => Err(e),
+    };
     
-    Ok(())
+    match result {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            Ok(ExitCode::FAILURE)
+        }
+    }
 }
 
+/// Whether raw mode and the alternate screen are on, so the panic hook knows whether
+/// there is anything to undo
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
+    TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
@@ -53,21 +106,23 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     Ok(terminal)
 }
 
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+/// Leave raw mode, the alternate screen and mouse capture. This writes to stdout
+/// directly rather than through the `Terminal`, so the panic hook can use it too.
+fn restore_terminal() -> Result<()> {
+    // Only the first call does anything, e.g. when a worker thread panics after quitting
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
     Ok(())
 }
 
 fn setup_panic_handler() {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
-        let _ = restore_terminal(&mut setup_terminal().unwrap());
+        // Back on the normal screen first, so the panic message stays visible
+        let _ = restore_terminal();
         original_hook(panic_info);
     }));
-}
\ No newline at end of file
+}
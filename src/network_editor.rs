@@ -1,6 +1,62 @@
-use crate::settings_editor::{EditorType, SettingEditor, SettingOption, SettingValue};
+use crate::settings_editor::{init_com, CommandExt, EditorType, SettingEditor, SettingOption, SettingValue};
 use anyhow::{Context, Result};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
 use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+use windows::Devices::Radios::{Radio, RadioAccessStatus, RadioKind, RadioState};
+
+/// Option value that switches the DNS editor into free-form entry
+const CUSTOM_DNS: &str = "custom";
+
+/// Physical medium of a network adapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdapterMedia {
+    Wireless,
+    Wired,
+    Other,
+}
+
+/// A network adapter discovered on the system
+#[derive(Debug, Clone)]
+pub struct NetworkAdapter {
+    pub name: String,
+    pub description: String,
+    pub media: AdapterMedia,
+}
+
+/// Enumerate the system's network adapters with `Get-NetAdapter`
+pub fn list_network_adapters() -> Result<Vec<NetworkAdapter>> {
+    let script = r#"
+        ConvertTo-Json -InputObject @(Get-NetAdapter | Select-Object Name, InterfaceDescription, PhysicalMediaType)
+    "#;
+    
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .checked_output("Failed to enumerate network adapters")?;
+    
+    let adapters: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)
+        .context("Failed to parse network adapters")?;
+    
+    Ok(adapters.iter()
+        .filter_map(|a| {
+            let name = a["Name"].as_str()?;
+            let media_type = a["PhysicalMediaType"].as_str().unwrap_or("");
+            let media = if media_type.contains("802.11") || media_type.contains("Wireless") {
+                AdapterMedia::Wireless
+            } else if media_type.contains("802.3") {
+                AdapterMedia::Wired
+            } else {
+                AdapterMedia::Other
+            };
+            Some(NetworkAdapter {
+                name: name.to_string(),
+                description: a["InterfaceDescription"].as_str().unwrap_or("").to_string(),
+                media,
+            })
+        })
+        .collect())
+}
 
 /// Network adapter enable/disable editor
 #[derive(Debug, Clone)]
@@ -13,30 +69,62 @@ impl NetworkAdapterToggleEditor {
         Self { adapter_name }
     }
     
-    fn is_adapter_enabled(&self) -> Result<bool> {
+    fn adapter_state(&self) -> Result<AdapterState> {
         let output = Command::new("netsh")
             .args(&["interface", "show", "interface", &self.adapter_name])
-            .output()
-            .context("Failed to query network adapter status")?;
+            .checked_output("Failed to query network adapter status")?;
         
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        Ok(output_str.contains("Connected") || output_str.contains("Enabled"))
+        parse_adapter_state(&String::from_utf8_lossy(&output.stdout))
+            .context("Could not find the adapter state in netsh output")
     }
 }
 
+/// Whether an adapter is switched on, and whether it also has a link (a cable plugged in
+/// or a Wi-Fi network joined), which an enabled adapter may not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AdapterState {
+    enabled: bool,
+    connected: bool,
+}
+
+/// Extract the adapter state from `netsh interface show interface <name>`, which prints
+/// the administrative state as Enabled or Disabled and the connect state as Connected
+/// or Disconnected on lines of their own
+fn parse_adapter_state(output: &str) -> Option<AdapterState> {
+    let values: Vec<&str> = output
+        .lines()
+        .filter_map(|line| Some(line.split_once(':')?.1.trim()))
+        .collect();
+    let has = |word: &str| values.iter().any(|value| value.eq_ignore_ascii_case(word));
+    let enabled = if has("Enabled") {
+        true
+    } else if has("Disabled") {
+        false
+    } else {
+        return None;
+    };
+    Some(AdapterState { enabled, connected: has("Connected") })
+}
+
 impl SettingEditor for NetworkAdapterToggleEditor {
     fn clone_box(&self) -> Box<dyn SettingEditor> {
         Box::new(self.clone())
     }
 
     fn get_current_value(&self) -> Result<SettingValue> {
-        Ok(SettingValue::Bool(self.is_adapter_enabled()?))
+        Ok(SettingValue::Bool(self.adapter_state()?.enabled))
     }
     
     fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        // The link state goes in the label, so an enabled adapter without internet says why
+        let enabled_label = match self.adapter_state() {
+            Ok(AdapterState { enabled: true, connected: true }) => "Enabled (connected)",
+            Ok(AdapterState { enabled: true, connected: false }) => "Enabled (disconnected)",
+            _ => "Enabled",
+        };
         Ok(vec![
             SettingOption {
-                label: "Enabled".to_string(),
+                label: enabled_label.to_string(),
                 value: SettingValue::Bool(true),
                 description: Some("Enable the network adapter".to_string()),
             },
@@ -54,8 +142,7 @@ impl SettingEditor for NetworkAdapterToggleEditor {
             
             Command::new("netsh")
                 .args(&["interface", "set", "interface", &self.adapter_name, action])
-                .output()
-                .context("Failed to change network adapter state")?;
+                .checked_output("Failed to change network adapter state")?;
             
             Ok(())
         } else {
@@ -74,6 +161,11 @@ impl SettingEditor for NetworkAdapterToggleEditor {
     fn requires_admin(&self) -> bool {
         true
     }
+    
+    fn is_destructive(&self) -> bool {
+        // Disabling the active adapter drops remote sessions
+        true
+    }
 }
 
 /// DNS server configuration editor
@@ -90,24 +182,153 @@ impl DNSServerEditor {
     fn get_current_dns(&self) -> Result<String> {
         let output = Command::new("netsh")
             .args(&["interface", "ip", "show", "dns", &self.adapter_name])
-            .output()
-            .context("Failed to get DNS servers")?;
+            .checked_output("Failed to get DNS servers")?;
+        let ipv4 = parse_dns_config(&String::from_utf8_lossy(&output.stdout));
         
-        let output_str = String::from_utf8_lossy(&output.stdout);
+        // Adapters with IPv6 turned off fail this, which just means there are no IPv6 servers
+        let ipv6 = Command::new("netsh")
+            .args(["interface", "ipv6", "show", "dnsservers", &self.adapter_name])
+            .checked_output("Failed to get IPv6 DNS servers")
+            .map(|output| parse_dns_config(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_else(|_| "dhcp".to_string());
         
-        // Parse DNS servers from output
-        for line in output_str.lines() {
-            if line.contains("DNS Servers") || line.contains("Statically Configured DNS Servers") {
-                if let Some(dns) = line.split(':').nth(1) {
-                    return Ok(dns.trim().to_string());
-                }
+        let configured: Vec<String> = [ipv4, ipv6].into_iter().filter(|servers| servers != "dhcp").collect();
+        if configured.is_empty() {
+            return Ok("dhcp".to_string());
+        }
+        Ok(configured.join(","))
+    }
+    
+    /// Make `servers` the DNS servers of one address family, or hand the family back to DHCP
+    /// when there are none
+    fn set_family_servers(&self, ipv6: bool, servers: &[IpAddr]) -> Result<()> {
+        for (args, error) in dns_family_commands(&self.adapter_name, ipv6, servers) {
+            let result = Command::new("netsh").args(args).checked_output(error);
+            // Fails on adapters with IPv6 turned off, which have no IPv6 servers to reset
+            if !(ipv6 && servers.is_empty()) {
+                result?;
             }
         }
-        
-        Ok("Automatic (DHCP)".to_string())
+        Ok(())
     }
 }
 
+/// The netsh arguments that make `servers` the DNS servers of one address family, each with
+/// the error reported if it fails. IPv4 goes through `netsh interface ip` and IPv6 through
+/// `netsh interface ipv6`. Without servers the family gets its servers from DHCP again.
+fn dns_family_commands(adapter_name: &str, ipv6: bool, servers: &[IpAddr]) -> Vec<(Vec<String>, &'static str)> {
+    let (family, context) = if ipv6 { ("ipv6", "dnsservers") } else { ("ip", "dns") };
+    let command = |args: &[&str]| -> Vec<String> {
+        ["interface", family].iter().chain(args).map(|arg| arg.to_string()).collect()
+    };
+    let servers: Vec<String> = servers.iter().map(|ip| ip.to_string()).collect();
+    
+    let Some(primary) = servers.first() else {
+        return vec![(command(&["set", context, adapter_name, "dhcp"]), "Failed to set DNS to automatic")];
+    };
+    let mut commands = vec![(command(&["set", context, adapter_name, "static", primary]), "Failed to set primary DNS")];
+    if let Some(secondary) = servers.get(1) {
+        commands.push((command(&["add", context, adapter_name, secondary, "index=2"]), "Failed to set secondary DNS"));
+    }
+    commands
+}
+
+/// Extract the DNS configuration from `netsh interface ip show dns`: `dhcp` when the
+/// servers come from DHCP, otherwise every server comma-separated like the option values.
+///
+/// The labels are translated on non-English installs, so the servers are taken from the
+/// first label mentioning DNS and the lines under it holding nothing but an address.
+/// That label names DHCP when the servers came from it, which holds across translations.
+fn parse_dns_config(output: &str) -> String {
+    let mut servers = Vec::new();
+    let mut block: Option<(bool, &str)> = None;
+    for line in output.lines().map(str::trim) {
+        if let Ok(address) = IpAddr::from_str(line) {
+            // Second and later servers are listed alone under the first
+            if block.is_some() {
+                servers.push(address.to_string());
+            }
+            continue;
+        }
+        // The label ends at ": ", which an IPv6 address never contains
+        let Some((label, value)) = line.split_once(": ").or_else(|| Some((line.strip_suffix(':')?, ""))) else {
+            continue;
+        };
+        if block.is_some() {
+            break;
+        }
+        if label.to_ascii_uppercase().contains("DNS") {
+            let value = value.trim();
+            if let Some(first) = value.split_whitespace().next().and_then(|first| IpAddr::from_str(first).ok()) {
+                servers.push(first.to_string());
+            }
+            block = Some((label.to_ascii_uppercase().contains("DHCP"), value));
+        }
+    }
+    
+    match block {
+        // Matches the "Automatic (DHCP)" option value
+        None | Some((true, _)) => "dhcp".to_string(),
+        // Statically configured without any servers, shown as netsh words it, e.g. "None"
+        Some((false, value)) if servers.is_empty() => value.to_string(),
+        Some((false, _)) => servers.join(","),
+    }
+}
+
+/// Empty the resolver cache with `ipconfig /flushdns`, so names are looked up again
+/// through the current DNS servers
+pub fn flush_dns_cache() -> Result<()> {
+    Command::new("ipconfig")
+        .arg("/flushdns")
+        .checked_output("DNS servers changed, but flushing the DNS cache failed")?;
+    Ok(())
+}
+
+/// How long a DNS server gets to answer the reachability check
+const DNS_CHECK_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Whether `server` answers a DNS query for the root name servers within `DNS_CHECK_TIMEOUT`
+fn dns_server_responds(server: IpAddr) -> bool {
+    // Header with a fixed ID, recursion desired and one question: the root, type NS, class IN
+    const QUERY: [u8; 17] = [0x54, 0x4d, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01];
+    let local: SocketAddr = if server.is_ipv6() {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    };
+    let probe = || -> std::io::Result<bool> {
+        let socket = UdpSocket::bind(local)?;
+        socket.set_read_timeout(Some(DNS_CHECK_TIMEOUT))?;
+        // Connected, so only the server's own answer is received
+        socket.connect((server, 53))?;
+        socket.send(&QUERY)?;
+        let mut response = [0; 512];
+        let len = socket.recv(&mut response)?;
+        Ok(len >= 2 && response[..2] == QUERY[..2])
+    };
+    probe().unwrap_or(false)
+}
+
+/// Parse comma-separated DNS server addresses, up to two of each family, into the
+/// IPv4 and the IPv6 servers
+fn parse_dns_servers(servers: &str) -> Result<(Vec<IpAddr>, Vec<IpAddr>)> {
+    let addresses = servers
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| IpAddr::from_str(s).with_context(|| format!("'{}' is not a valid IP address", s)))
+        .collect::<Result<Vec<_>>>()?;
+    if addresses.is_empty() {
+        anyhow::bail!("Enter at least one DNS server");
+    }
+    
+    let (ipv4, ipv6): (Vec<IpAddr>, Vec<IpAddr>) = addresses.into_iter().partition(IpAddr::is_ipv4);
+    if ipv4.len() > 2 || ipv6.len() > 2 {
+        anyhow::bail!("Enter at most two IPv4 and two IPv6 DNS servers");
+    }
+    Ok((ipv4, ipv6))
+}
+
 impl SettingEditor for DNSServerEditor {
     fn clone_box(&self) -> Box<dyn SettingEditor> {
         Box::new(self.clone())
@@ -134,6 +355,11 @@ impl SettingEditor for DNSServerEditor {
                 value: SettingValue::Selection("1.1.1.1,1.0.0.1".to_string()),
                 description: Some("1.1.1.1, 1.0.0.1 - Privacy focused".to_string()),
             },
+            SettingOption {
+                label: "Cloudflare DNS (IPv6)".to_string(),
+                value: SettingValue::Selection("2606:4700:4700::1111,2606:4700:4700::1001".to_string()),
+                description: Some("2606:4700:4700::1111, 2606:4700:4700::1001".to_string()),
+            },
             SettingOption {
                 label: "OpenDNS".to_string(),
                 value: SettingValue::Selection("208.67.222.222,208.67.220.220".to_string()),
@@ -144,52 +370,45 @@ impl SettingEditor for DNSServerEditor {
                 value: SettingValue::Selection("9.9.9.9,149.112.112.112".to_string()),
                 description: Some("9.9.9.9, 149.112.112.112 - Security focused".to_string()),
             },
+            SettingOption {
+                label: "Custom...".to_string(),
+                value: SettingValue::Selection(CUSTOM_DNS.to_string()),
+                description: Some("Enter up to two IPv4 and two IPv6 server addresses".to_string()),
+            },
         ])
     }
     
     fn set_value(&self, value: SettingValue) -> Result<()> {
-        if let SettingValue::Selection(dns_config) = value {
-            if dns_config == "dhcp" {
-                // Set to automatic
-                Command::new("netsh")
-                    .args(&["interface", "ip", "set", "dns", &self.adapter_name, "dhcp"])
-                    .output()
-                    .context("Failed to set DNS to automatic")?;
+        if let SettingValue::Selection(dns_config) | SettingValue::String(dns_config) = value {
+            // A family without servers in the value goes back to DHCP, so servers set earlier
+            // don't linger next to the new ones
+            let (ipv4, ipv6) = if dns_config == "dhcp" {
+                (vec![], vec![])
             } else {
-                // Set static DNS servers
-                let servers: Vec<&str> = dns_config.split(',').collect();
-                
-                // Set primary DNS
-                if let Some(primary) = servers.get(0) {
-                    Command::new("netsh")
-                        .args(&[
-                            "interface", "ip", "set", "dns", 
-                            &self.adapter_name, "static", primary.trim()
-                        ])
-                        .output()
-                        .context("Failed to set primary DNS")?;
-                }
-                
-                // Add secondary DNS
-                if let Some(secondary) = servers.get(1) {
-                    Command::new("netsh")
-                        .args(&[
-                            "interface", "ip", "add", "dns", 
-                            &self.adapter_name, secondary.trim(), "index=2"
-                        ])
-                        .output()
-                        .context("Failed to set secondary DNS")?;
-                }
-            }
+                parse_dns_servers(&dns_config)?
+            };
+            self.set_family_servers(false, &ipv4)?;
+            self.set_family_servers(true, &ipv6)?;
             
-            Ok(())
+            // Otherwise lookups cached from the old servers keep being answered until they expire
+            flush_dns_cache()
         } else {
             anyhow::bail!("Invalid value type for DNS configuration")
         }
     }
     
     fn validate_value(&self, value: &SettingValue) -> Result<bool> {
-        Ok(matches!(value, SettingValue::Selection(_)))
+        match value {
+            SettingValue::Selection(dns_config) if dns_config == "dhcp" => Ok(true),
+            SettingValue::Selection(dns_config) if dns_config == CUSTOM_DNS => {
+                anyhow::bail!("Enter a custom DNS server address")
+            }
+            SettingValue::Selection(dns_config) | SettingValue::String(dns_config) => {
+                parse_dns_servers(dns_config)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
     
     fn get_editor_type(&self) -> EditorType {
@@ -199,103 +418,193 @@ impl SettingEditor for DNSServerEditor {
     fn requires_admin(&self) -> bool {
         true
     }
+    
+    fn apply_note(&self) -> Option<&'static str> {
+        Some("DNS cache flushed, new lookups use the new servers")
+    }
+    
+    fn custom_entry_editor(&self, value: &SettingValue) -> Option<EditorType> {
+        match value {
+            SettingValue::Selection(s) if s == CUSTOM_DNS => Some(EditorType::TextInput { multiline: false }),
+            _ => None,
+        }
+    }
+    
+    fn check_value(&self, value: &SettingValue) -> Option<String> {
+        // Only presets are probed: DHCP and "Custom..." don't parse as servers, and typed
+        // servers change with every key
+        let SettingValue::Selection(servers) = value else {
+            return None;
+        };
+        let (ipv4, ipv6) = parse_dns_servers(servers).ok()?;
+        let primary = *ipv4.first().or(ipv6.first())?;
+        (!dns_server_responds(primary)).then(|| format!("{} did not respond", primary))
+    }
 }
 
-/// Wi-Fi power management editor
-#[derive(Debug, Clone)]
-pub struct WiFiPowerEditor;
+/// Power states a Wi-Fi power saving mode applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum PowerStates {
+    #[default]
+    Both,
+    PluggedIn,
+    OnBattery,
+}
+
+/// Option values that switch the editor to a single power state
+const PLUGGED_IN_ONLY: &str = "plugged_in";
+const ON_BATTERY_ONLY: &str = "on_battery";
+
+/// Wi-Fi power saving modes by setting index, with their descriptions
+const WIFI_POWER_MODES: [(&str, &str); 4] = [
+    ("Maximum Performance", "No power saving, best performance"),
+    ("Low Power Saving", "Minimal power saving"),
+    ("Medium Power Saving", "Balanced power saving"),
+    ("Maximum Power Saving", "Maximum power saving, may affect performance"),
+];
+
+/// Wi-Fi power management editor. Sets plugged in and on battery together by default,
+/// with options to switch to just one of them.
+#[derive(Debug, Clone, Default)]
+pub struct WiFiPowerEditor {
+    states: PowerStates,
+}
 
 impl WiFiPowerEditor {
     pub fn new() -> Self {
-        Self
+        Self { states: PowerStates::Both }
     }
     
-    fn get_power_saving_mode(&self) -> Result<String> {
+    fn for_states(states: PowerStates) -> Self {
+        Self { states }
+    }
+    
+    /// Current mode indices when plugged in and on battery, in the same form as the option values
+    fn get_power_saving_modes(&self) -> Result<(String, String)> {
         let output = Command::new("powercfg")
-            .args(&["/q", "SCHEME_CURRENT", "19cbb8fa-5279-450e-9fac-8a3d5fedd0c1"])
-            .output()
-            .context("Failed to get Wi-Fi power settings")?;
+            .args(["/q", "SCHEME_CURRENT", WIFI_POWER_SUBGROUP, WIFI_POWER_SETTING])
+            .checked_output("Failed to get Wi-Fi power settings")?;
         
         let output_str = String::from_utf8_lossy(&output.stdout);
         
-        // Parse the power saving mode from output
-        if output_str.contains("Power Saving Mode: Maximum Performance") {
-            Ok("Maximum Performance".to_string())
-        } else if output_str.contains("Power Saving Mode: Low Power Saving") {
-            Ok("Low Power Saving".to_string())
-        } else if output_str.contains("Power Saving Mode: Medium Power Saving") {
-            Ok("Medium Power Saving".to_string())
-        } else {
-            Ok("Maximum Power Saving".to_string())
+        let indices = parse_current_indices(&output_str);
+        let [ac, dc] = indices[..] else {
+            anyhow::bail!("Could not find the current Wi-Fi power setting indices");
+        };
+        for index in [ac, dc] {
+            if index >= WIFI_POWER_MODES.len() {
+                anyhow::bail!("Unknown Wi-Fi power mode index {}", index);
+            }
         }
+        
+        Ok((ac.to_string(), dc.to_string()))
     }
 }
 
+/// Wireless Adapter Settings subgroup and its Power Saving Mode setting
+const WIFI_POWER_SUBGROUP: &str = "19cbb8fa-5279-450e-9fac-8a3d5fedd0c1";
+const WIFI_POWER_SETTING: &str = "12bbebe6-58d6-4636-95bb-3217ef867c1a";
+
+/// Extract the current AC and DC setting indices from `powercfg /q` output.
+///
+/// The labels are translated on non-English installs, but the setting values
+/// are always printed as hex with the AC value first, so only those are parsed.
+fn parse_current_indices(output: &str) -> Vec<usize> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter_map(|(_, value)| {
+            let hex = value.trim().strip_prefix("0x")?;
+            usize::from_str_radix(hex, 16).ok()
+        })
+        .collect()
+}
+
+fn wifi_power_mode_label(mode: &str) -> &'static str {
+    mode.parse::<usize>()
+        .ok()
+        .and_then(|index| WIFI_POWER_MODES.get(index))
+        .map_or("Unknown", |(label, _)| label)
+}
+
 impl SettingEditor for WiFiPowerEditor {
     fn clone_box(&self) -> Box<dyn SettingEditor> {
         Box::new(self.clone())
     }
 
     fn get_current_value(&self) -> Result<SettingValue> {
-        Ok(SettingValue::Selection(self.get_power_saving_mode()?))
+        let (ac, dc) = self.get_power_saving_modes()?;
+        // Both states are set together by default, so the plugged in one speaks for them
+        Ok(SettingValue::Selection(match self.states {
+            PowerStates::Both | PowerStates::PluggedIn => ac,
+            PowerStates::OnBattery => dc,
+        }))
     }
     
     fn get_available_options(&self) -> Result<Vec<SettingOption>> {
-        Ok(vec![
-            SettingOption {
-                label: "Maximum Performance".to_string(),
-                value: SettingValue::Selection("0".to_string()),
-                description: Some("No power saving, best performance".to_string()),
-            },
-            SettingOption {
-                label: "Low Power Saving".to_string(),
-                value: SettingValue::Selection("1".to_string()),
-                description: Some("Minimal power saving".to_string()),
-            },
-            SettingOption {
-                label: "Medium Power Saving".to_string(),
-                value: SettingValue::Selection("2".to_string()),
-                description: Some("Balanced power saving".to_string()),
-            },
-            SettingOption {
-                label: "Maximum Power Saving".to_string(),
-                value: SettingValue::Selection("3".to_string()),
-                description: Some("Maximum power saving, may affect performance".to_string()),
-            },
-        ])
+        let applies_to = match self.states {
+            PowerStates::Both => "",
+            PowerStates::PluggedIn => " (plugged in only)",
+            PowerStates::OnBattery => " (on battery only)",
+        };
+        let mut options: Vec<SettingOption> = WIFI_POWER_MODES
+            .iter()
+            .enumerate()
+            .map(|(index, (label, description))| SettingOption {
+                label: label.to_string(),
+                value: SettingValue::Selection(index.to_string()),
+                description: Some(format!("{}{}", description, applies_to)),
+            })
+            .collect();
+        
+        if self.states == PowerStates::Both {
+            let (ac, dc) = self.get_power_saving_modes()?;
+            // Only shown when it tells the user something the highlighted option doesn't
+            let differs = if ac != dc { ", unlike plugged in" } else { "" };
+            options.push(SettingOption {
+                label: "Plugged in only...".to_string(),
+                value: SettingValue::Selection(PLUGGED_IN_ONLY.to_string()),
+                description: Some(format!("Currently {}", wifi_power_mode_label(&ac))),
+            });
+            options.push(SettingOption {
+                label: "On battery only...".to_string(),
+                value: SettingValue::Selection(ON_BATTERY_ONLY.to_string()),
+                description: Some(format!("Currently {}{}", wifi_power_mode_label(&dc), differs)),
+            });
+        }
+        Ok(options)
     }
     
     fn set_value(&self, value: SettingValue) -> Result<()> {
         if let SettingValue::Selection(mode) = value {
-            // Set for AC power
-            Command::new("powercfg")
-                .args(&[
-                    "/setacvalueindex",
-                    "SCHEME_CURRENT",
-                    "19cbb8fa-5279-450e-9fac-8a3d5fedd0c1",
-                    "12bbebe6-58d6-4636-95bb-3217ef867c1a",
-                    &mode,
-                ])
-                .output()
-                .context("Failed to set Wi-Fi power mode for AC")?;
+            if self.states != PowerStates::OnBattery {
+                Command::new("powercfg")
+                    .args([
+                        "/setacvalueindex",
+                        "SCHEME_CURRENT",
+                        WIFI_POWER_SUBGROUP,
+                        WIFI_POWER_SETTING,
+                        &mode,
+                    ])
+                    .checked_output("Failed to set Wi-Fi power mode for AC")?;
+            }
             
-            // Set for DC power (battery)
-            Command::new("powercfg")
-                .args(&[
-                    "/setdcvalueindex",
-                    "SCHEME_CURRENT",
-                    "19cbb8fa-5279-450e-9fac-8a3d5fedd0c1",
-                    "12bbebe6-58d6-4636-95bb-3217ef867c1a",
-                    &mode,
-                ])
-                .output()
-                .context("Failed to set Wi-Fi power mode for battery")?;
+            if self.states != PowerStates::PluggedIn {
+                Command::new("powercfg")
+                    .args([
+                        "/setdcvalueindex",
+                        "SCHEME_CURRENT",
+                        WIFI_POWER_SUBGROUP,
+                        WIFI_POWER_SETTING,
+                        &mode,
+                    ])
+                    .checked_output("Failed to set Wi-Fi power mode for battery")?;
+            }
             
             // Apply the changes
             Command::new("powercfg")
                 .args(&["/setactive", "SCHEME_CURRENT"])
-                .output()
-                .context("Failed to apply power settings")?;
+                .checked_output("Failed to apply power settings")?;
             
             Ok(())
         } else {
@@ -318,4 +627,319 @@ impl SettingEditor for WiFiPowerEditor {
     fn requires_admin(&self) -> bool {
         true
     }
-}
\ No newline at end of file
+    
+    fn select_target(&self, value: &SettingValue) -> Option<Box<dyn SettingEditor>> {
+        let states = match value {
+            SettingValue::Selection(state) if state == PLUGGED_IN_ONLY => PowerStates::PluggedIn,
+            SettingValue::Selection(state) if state == ON_BATTERY_ONLY => PowerStates::OnBattery,
+            _ => return None,
+        };
+        Some(Box::new(WiFiPowerEditor::for_states(states)))
+    }
+}
+/// Windows Defender Firewall on/off for the active network profile
+#[derive(Debug, Clone, Default)]
+pub struct FirewallEditor;
+
+impl FirewallEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Extract the firewall state from `netsh advfirewall show currentprofile state`.
+///
+/// The "State" label is translated on non-English installs, but the value is
+/// always printed as ON or OFF, so the first line ending in one of them is used.
+fn parse_firewall_state(output: &str) -> Option<bool> {
+    output.lines().find_map(|line| match line.split_whitespace().last()? {
+        value if value.eq_ignore_ascii_case("ON") => Some(true),
+        value if value.eq_ignore_ascii_case("OFF") => Some(false),
+        _ => None,
+    })
+}
+
+impl SettingEditor for FirewallEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let output = Command::new("netsh")
+            .args(["advfirewall", "show", "currentprofile", "state"])
+            .checked_output("Failed to query firewall state")?;
+        
+        let enabled = parse_firewall_state(&String::from_utf8_lossy(&output.stdout))
+            .context("Could not find the firewall state in netsh output")?;
+        Ok(SettingValue::Bool(enabled))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![
+            SettingOption {
+                label: "Enabled".to_string(),
+                value: SettingValue::Bool(true),
+                description: Some("Filter traffic for the current network profile".to_string()),
+            },
+            SettingOption {
+                label: "Disabled".to_string(),
+                value: SettingValue::Bool(false),
+                description: Some("Allow all traffic - leaves this PC exposed on the network".to_string()),
+            },
+        ])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Bool(enable) = value {
+            let state = if enable { "on" } else { "off" };
+            
+            Command::new("netsh")
+                .args(["advfirewall", "set", "currentprofile", "state", state])
+                .checked_output("Failed to change firewall state")?;
+            
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for firewall")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        Ok(matches!(value, SettingValue::Bool(_)))
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Toggle
+    }
+    
+    fn requires_admin(&self) -> bool {
+        // Also makes every change go through the confirmation dialog
+        true
+    }
+}
+
+/// Bluetooth radio on/off through the WinRT `Radio` API, as there is no shell command for it
+#[derive(Debug, Clone, Default)]
+pub struct BluetoothEditor;
+
+impl BluetoothEditor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    fn get_radio(&self) -> Result<Radio> {
+        init_com();
+        
+        let radios = Radio::GetRadiosAsync()
+            .and_then(|operation| operation.get())
+            .context("Failed to enumerate radios")?;
+        for radio in radios {
+            if radio.Kind()? == RadioKind::Bluetooth {
+                return Ok(radio);
+            }
+        }
+        anyhow::bail!("No Bluetooth radio was found on this PC")
+    }
+}
+
+impl SettingEditor for BluetoothEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let state = self.get_radio()?.State().context("Failed to read Bluetooth state")?;
+        Ok(SettingValue::Bool(state == RadioState::On))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![
+            SettingOption {
+                label: "On".to_string(),
+                value: SettingValue::Bool(true),
+                description: Some("Turn the Bluetooth radio on".to_string()),
+            },
+            SettingOption {
+                label: "Off".to_string(),
+                value: SettingValue::Bool(false),
+                description: Some("Turn the Bluetooth radio off, disconnecting paired devices".to_string()),
+            },
+        ])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Bool(enable) = value {
+            let radio = self.get_radio()?;
+            
+            let access = Radio::RequestAccessAsync()
+                .and_then(|operation| operation.get())
+                .context("Failed to request access to the Bluetooth radio")?;
+            if access != RadioAccessStatus::Allowed {
+                anyhow::bail!("Windows denied access to the Bluetooth radio - check Settings > Privacy > Radios");
+            }
+            
+            let state = if enable { RadioState::On } else { RadioState::Off };
+            let status = radio
+                .SetStateAsync(state)
+                .and_then(|operation| operation.get())
+                .context("Failed to change Bluetooth state")?;
+            if status != RadioAccessStatus::Allowed {
+                anyhow::bail!("Windows refused to change the Bluetooth state");
+            }
+            
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for Bluetooth")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        Ok(matches!(value, SettingValue::Bool(_)))
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Toggle
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH_QUERY: &str = "\
+Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced)
+  Subgroup GUID: 19cbb8fa-5279-450e-9fac-8a3d5fedd0c1  (Wireless Adapter Settings)
+    Power Setting GUID: 12bbebe6-58d6-4636-95bb-3217ef867c1a  (Power Saving Mode)
+      Possible Setting Index: 000
+      Possible Setting Friendly Name: Maximum Performance
+      Possible Setting Index: 001
+      Possible Setting Friendly Name: Low Power Saving
+      Possible Setting Index: 002
+      Possible Setting Friendly Name: Medium Power Saving
+      Possible Setting Index: 003
+      Possible Setting Friendly Name: Maximum Power Saving
+    Current AC Power Setting Index: 0x00000000
+    Current DC Power Setting Index: 0x00000002
+";
+
+    const GERMAN_QUERY: &str = "\
+GUID des Energieschemas: 381b4222-f694-41f0-9685-ff5bb260df2e  (Ausbalanciert)
+  GUID der Untergruppe: 19cbb8fa-5279-450e-9fac-8a3d5fedd0c1  (Einstellungen für drahtlose Adapter)
+    GUID der Energieeinstellung: 12bbebe6-58d6-4636-95bb-3217ef867c1a  (Energiesparmodus)
+      Index der möglichen Einstellung: 000
+      Angezeigter Name der möglichen Einstellung: Höchstleistung
+      Index der möglichen Einstellung: 003
+      Angezeigter Name der möglichen Einstellung: Maximaler Energiespareffekt
+    Index der aktuellen Wechselstromeinstellung: 0x00000001
+    Index der aktuellen Gleichstromeinstellung: 0x00000003
+";
+
+    #[test]
+    fn parses_english_power_indices() {
+        assert_eq!(parse_current_indices(ENGLISH_QUERY), vec![0, 2]);
+    }
+
+    #[test]
+    fn parses_localized_power_indices() {
+        assert_eq!(parse_current_indices(GERMAN_QUERY), vec![1, 3]);
+    }
+
+    #[test]
+    fn finds_no_indices_in_unrelated_output() {
+        assert!(parse_current_indices("The system cannot find the file specified.").is_empty());
+    }
+
+    #[test]
+    fn parses_static_servers_on_continuation_lines() {
+        let output = "
+Configuration for interface \"Ethernet\"
+    Statically Configured DNS Servers:    1.1.1.1
+                                          1.0.0.1
+    Register with which suffix:           Primary only
+";
+        assert_eq!(parse_dns_config(output), "1.1.1.1,1.0.0.1");
+    }
+
+    #[test]
+    fn parses_dhcp_configured_servers() {
+        let output = "
+Configuration for interface \"Wi-Fi\"
+    DNS servers configured through DHCP:  192.168.1.1
+                                          fd00::1
+    Register with which suffix:           Primary only
+";
+        assert_eq!(parse_dns_config(output), "dhcp");
+    }
+
+    #[test]
+    fn parses_localized_servers() {
+        let output = "
+Konfiguration der Schnittstelle \"Ethernet\"
+    Statisch konfigurierte DNS-Server:    9.9.9.9
+                                          149.112.112.112
+    Mit folgendem Suffix registrieren:    Nur primär
+";
+        assert_eq!(parse_dns_config(output), "9.9.9.9,149.112.112.112");
+    }
+
+    #[test]
+    fn keeps_the_wording_of_static_configs_without_servers() {
+        let output = "
+Configuration for interface \"Ethernet\"
+    Statically Configured DNS Servers:    None
+    Register with which suffix:           Primary only
+";
+        assert_eq!(parse_dns_config(output), "None");
+    }
+
+    #[test]
+    fn stops_at_the_line_after_the_servers() {
+        let output = "
+Configuration for interface \"Ethernet\"
+    Statically Configured DNS Servers:    8.8.8.8
+    Register with which suffix:           Primary only
+
+Configuration for interface \"Loopback Pseudo-Interface 1\"
+    Statically Configured DNS Servers:    127.0.0.1
+                                          ::1
+";
+        assert_eq!(parse_dns_config(output), "8.8.8.8");
+    }
+
+    /// Just the netsh arguments, joined for comparison
+    fn family_commands(ipv6: bool, servers: &str) -> Vec<String> {
+        let servers: Vec<IpAddr> = servers.split(',').filter_map(|ip| ip.parse().ok()).collect();
+        dns_family_commands("Ethernet", ipv6, &servers)
+            .into_iter()
+            .map(|(args, _)| args.join(" "))
+            .collect()
+    }
+
+    #[test]
+    fn sets_both_servers_of_a_family() {
+        assert_eq!(
+            family_commands(false, "8.8.8.8,8.8.4.4"),
+            vec![
+                "interface ip set dns Ethernet static 8.8.8.8",
+                "interface ip add dns Ethernet 8.8.4.4 index=2",
+            ]
+        );
+    }
+
+    #[test]
+    fn hands_a_family_missing_from_the_value_back_to_dhcp() {
+        let (ipv4, ipv6) = parse_dns_servers("2606:4700:4700::1111").unwrap();
+        assert_eq!(
+            dns_family_commands("Ethernet", false, &ipv4)[0].0.join(" "),
+            "interface ip set dns Ethernet dhcp"
+        );
+        assert_eq!(
+            dns_family_commands("Ethernet", true, &ipv6)[0].0.join(" "),
+            "interface ipv6 set dnsservers Ethernet static 2606:4700:4700::1111"
+        );
+        assert_eq!(family_commands(true, ""), vec!["interface ipv6 set dnsservers Ethernet dhcp"]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::registry::{broadcast_setting_change, read_dword, write_dword};
+use crate::settings_editor::{EditorType, SettingEditor, SettingOption, SettingValue};
+use anyhow::Result;
+use windows::Win32::System::Registry::HKEY_CURRENT_USER;
+
+const PERSONALIZE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+
+/// Light or dark mode for apps and the Windows shell, `Bool(true)` meaning light
+#[derive(Debug, Clone, Default)]
+pub struct AppThemeEditor;
+
+impl AppThemeEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SettingEditor for AppThemeEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        // The value only exists once the mode has been changed, and Windows treats that as dark
+        let light = read_dword(HKEY_CURRENT_USER, PERSONALIZE_KEY, "AppsUseLightTheme")?.unwrap_or(0);
+        Ok(SettingValue::Bool(light != 0))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![
+            SettingOption {
+                label: "Light".to_string(),
+                value: SettingValue::Bool(true),
+                description: Some("Light mode for apps and the taskbar".to_string()),
+            },
+            SettingOption {
+                label: "Dark".to_string(),
+                value: SettingValue::Bool(false),
+                description: Some("Dark mode for apps and the taskbar".to_string()),
+            },
+        ])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Bool(light) = value {
+            let data = u32::from(light);
+            write_dword(HKEY_CURRENT_USER, PERSONALIZE_KEY, "AppsUseLightTheme", data)?;
+            write_dword(HKEY_CURRENT_USER, PERSONALIZE_KEY, "SystemUsesLightTheme", data)?;
+            broadcast_setting_change("ImmersiveColorSet");
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for app theme")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        Ok(matches!(value, SettingValue::Bool(_)))
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Toggle
+    }
+    
+    fn requires_admin(&self) -> bool {
+        false
+    }
+}
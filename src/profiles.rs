@@ -0,0 +1,80 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+use crate::{
+    config::app_dir,
+    settings::SettingsItem,
+    settings_editor::SettingValue,
+    snapshot::{self, SnapshotEntry},
+};
+
+/// One setting a profile puts in place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileEntry {
+    pub editor_key: String,
+    pub value: SettingValue,
+}
+
+/// Named sets of setting values like "Gaming" or "Battery", keyed by profile name
+pub type Profiles = BTreeMap<String, Vec<ProfileEntry>>;
+
+pub fn path() -> Option<PathBuf> {
+    app_dir().map(|dir| dir.join("profiles.json"))
+}
+
+/// Load the saved profiles; a missing file just means none have been captured yet
+pub fn load() -> Result<Profiles> {
+    let Some(path) = path().filter(|path| path.exists()) else {
+        return Ok(Profiles::new());
+    };
+    let contents = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("{} is not a valid profiles file", path.display()))
+}
+
+pub fn save(profiles: &Profiles) -> Result<()> {
+    let path = path().context("Could not locate the application data folder")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create config folder")?;
+    }
+    let contents = serde_json::to_string_pretty(profiles)?;
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Capture the current value of every editable item into the profile `name`,
+/// replacing it if it exists. Returns how many settings were stored.
+pub fn capture(name: &str, items: &[SettingsItem]) -> Result<usize> {
+    // Settings that couldn't be read are left out rather than stored without a value
+    let entries: Vec<ProfileEntry> = snapshot::capture(items)
+        .into_iter()
+        .filter_map(|entry| {
+            Some(ProfileEntry {
+                value: entry.value?,
+                editor_key: entry.editor_key,
+            })
+        })
+        .collect();
+    let count = entries.len();
+
+    let mut profiles = load()?;
+    profiles.insert(name.to_string(), entries);
+    save(&profiles)?;
+    Ok(count)
+}
+
+/// Turn a profile into snapshot entries so it can go through `snapshot::apply`,
+/// naming each one after the item that uses its editor
+pub fn to_snapshot_entries(entries: &[ProfileEntry], items: &[SettingsItem]) -> Vec<SnapshotEntry> {
+    entries
+        .iter()
+        .map(|entry| SnapshotEntry {
+            name: items
+                .iter()
+                .find(|item| item.editor_key.as_deref() == Some(entry.editor_key.as_str()))
+                .map(|item| item.name.clone())
+                .unwrap_or_else(|| entry.editor_key.clone()),
+            editor_key: entry.editor_key.clone(),
+            value: Some(entry.value.clone()),
+            error: None,
+        })
+        .collect()
+}
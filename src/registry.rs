@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use windows::{
+    core::{PCWSTR, PWSTR},
+    Win32::Foundation::{ERROR_FILE_NOT_FOUND, ERROR_NO_MORE_ITEMS, LPARAM, WPARAM},
+    Win32::System::Registry::{
+        RegCloseKey, RegDeleteKeyValueW, RegEnumValueW, RegGetValueW, RegOpenKeyExW, RegSetKeyValueW, HKEY, KEY_READ,
+        REG_BINARY, REG_DWORD, REG_ROUTINE_FLAGS, REG_SZ, RRF_RT_REG_BINARY, RRF_RT_REG_DWORD, RRF_RT_REG_SZ,
+    },
+    Win32::UI::WindowsAndMessaging::{SendMessageTimeoutW, HWND_BROADCAST, SMTO_ABORTIFHUNG, WM_SETTINGCHANGE},
+};
+
+/// Read a DWORD value, or `None` if the value or its key doesn't exist yet
+pub fn read_dword(root: HKEY, subkey: &str, name: &str) -> Result<Option<u32>> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    let mut data = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut data as *mut u32 as *mut _),
+            Some(&mut size),
+        )
+    };
+    if status == ERROR_FILE_NOT_FOUND {
+        return Ok(None);
+    }
+    status
+        .ok()
+        .with_context(|| format!("Failed to read registry value {}\\{}", subkey, name))?;
+    Ok(Some(data))
+}
+
+/// Write a DWORD value, creating its key if needed
+pub fn write_dword(root: HKEY, subkey: &str, name: &str, value: u32) -> Result<()> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    
+    unsafe {
+        RegSetKeyValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            REG_DWORD.0,
+            Some(&value as *const u32 as *const _),
+            std::mem::size_of::<u32>() as u32,
+        )
+    }
+    .ok()
+    .with_context(|| format!("Failed to write registry value {}\\{}", subkey, name))
+}
+
+/// Read a string value, or `None` if the value or its key doesn't exist yet
+pub fn read_string(root: HKEY, subkey: &str, name: &str) -> Result<Option<String>> {
+    let Some(bytes) = read_raw(root, subkey, name, RRF_RT_REG_SZ)? else {
+        return Ok(None);
+    };
+    let wide: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    Ok(Some(String::from_utf16_lossy(&wide)))
+}
+
+/// Read a binary value, or `None` if the value or its key doesn't exist yet
+pub fn read_binary(root: HKEY, subkey: &str, name: &str) -> Result<Option<Vec<u8>>> {
+    read_raw(root, subkey, name, RRF_RT_REG_BINARY)
+}
+
+fn read_raw(root: HKEY, subkey: &str, name: &str, flags: REG_ROUTINE_FLAGS) -> Result<Option<Vec<u8>>> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    let context = || format!("Failed to read registry value {}\\{}", subkey, name);
+    
+    // The first call only reports the size in bytes
+    let mut size = 0u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            flags,
+            None,
+            None,
+            Some(&mut size),
+        )
+    };
+    if status == ERROR_FILE_NOT_FOUND {
+        return Ok(None);
+    }
+    status.ok().with_context(context)?;
+    
+    let mut buffer = vec![0u8; size as usize];
+    unsafe {
+        RegGetValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            flags,
+            None,
+            Some(buffer.as_mut_ptr() as *mut _),
+            Some(&mut size),
+        )
+    }
+    .ok()
+    .with_context(context)?;
+    buffer.truncate(size as usize);
+    Ok(Some(buffer))
+}
+
+/// Names of the values directly under a key; a key that doesn't exist has none
+pub fn list_value_names(root: HKEY, subkey: &str) -> Result<Vec<String>> {
+    let subkey_wide = to_wide(subkey);
+    let mut key = HKEY::default();
+    let status = unsafe { RegOpenKeyExW(root, PCWSTR(subkey_wide.as_ptr()), 0, KEY_READ, &mut key) };
+    if status == ERROR_FILE_NOT_FOUND {
+        return Ok(vec![]);
+    }
+    status
+        .ok()
+        .with_context(|| format!("Failed to open registry key {}", subkey))?;
+    
+    let mut names = Vec::new();
+    // Value names are limited to 16,383 characters
+    let mut buffer = vec![0u16; 16_384];
+    let result = loop {
+        let mut len = buffer.len() as u32;
+        let status = unsafe {
+            RegEnumValueW(key, names.len() as u32, PWSTR(buffer.as_mut_ptr()), &mut len, None, None, None, None)
+        };
+        if status == ERROR_NO_MORE_ITEMS {
+            break Ok(names);
+        }
+        if let Err(e) = status.ok() {
+            break Err(e).with_context(|| format!("Failed to list values of {}", subkey));
+        }
+        names.push(String::from_utf16_lossy(&buffer[..len as usize]));
+    };
+    unsafe {
+        let _ = RegCloseKey(key);
+    }
+    result
+}
+
+/// Write a string value, creating its key if needed
+pub fn write_string(root: HKEY, subkey: &str, name: &str, value: &str) -> Result<()> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    let value_wide = to_wide(value);
+    
+    unsafe {
+        RegSetKeyValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            REG_SZ.0,
+            Some(value_wide.as_ptr() as *const _),
+            (value_wide.len() * 2) as u32,
+        )
+    }
+    .ok()
+    .with_context(|| format!("Failed to write registry value {}\\{}", subkey, name))
+}
+
+/// Write a binary value, creating its key if needed
+pub fn write_binary(root: HKEY, subkey: &str, name: &str, value: &[u8]) -> Result<()> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    
+    unsafe {
+        RegSetKeyValueW(
+            root,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(name_wide.as_ptr()),
+            REG_BINARY.0,
+            Some(value.as_ptr() as *const _),
+            value.len() as u32,
+        )
+    }
+    .ok()
+    .with_context(|| format!("Failed to write registry value {}\\{}", subkey, name))
+}
+
+/// Delete a value; one that doesn't exist counts as deleted
+pub fn delete_value(root: HKEY, subkey: &str, name: &str) -> Result<()> {
+    let subkey_wide = to_wide(subkey);
+    let name_wide = to_wide(name);
+    
+    let status = unsafe { RegDeleteKeyValueW(root, PCWSTR(subkey_wide.as_ptr()), PCWSTR(name_wide.as_ptr())) };
+    if status == ERROR_FILE_NOT_FOUND {
+        return Ok(());
+    }
+    status
+        .ok()
+        .with_context(|| format!("Failed to delete registry value {}\\{}", subkey, name))
+}
+
+/// Broadcast `WM_SETTINGCHANGE` for `area` so Explorer and other running apps
+/// pick up a registry change without signing out
+pub fn broadcast_setting_change(area: &str) {
+    let area_wide = to_wide(area);
+    unsafe {
+        // Best effort - a hung window only means that app refreshes later
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            WPARAM(0),
+            LPARAM(area_wide.as_ptr() as isize),
+            SMTO_ABORTIFHUNG,
+            5000,
+            None,
+        );
+    }
+}
+
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
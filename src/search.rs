@@ -0,0 +1,121 @@
+use crate::settings::SettingsItem;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+/// Minimum score per query character for an item to count as a match
+const MIN_SCORE_PER_CHAR: i64 = 10;
+
+/// Keyword matches count this many percent of their score, so an item tagged with
+/// the query ranks above one that only mentions it in passing
+const KEYWORD_WEIGHT: i64 = 125;
+
+/// Everyday words and the terms Windows uses for them. A query that is one of these
+/// words also matches items containing any of its terms.
+const SYNONYMS: &[(&str, &[&str])] = &[
+    ("wifi", &["wi-fi", "wireless", "wlan"]),
+    ("internet", &["network", "wi-fi", "dns"]),
+    ("screen", &["display", "resolution", "monitor"]),
+    ("monitor", &["display", "resolution"]),
+    ("sound", &["audio", "volume", "speaker"]),
+    ("speakers", &["audio", "sound"]),
+    ("headphones", &["audio", "sound"]),
+    ("mic", &["microphone"]),
+    ("battery", &["power", "sleep"]),
+    ("wallpaper", &["background"]),
+    ("dark", &["light/dark", "theme"]),
+    ("clock", &["time", "date"]),
+    ("hostname", &["computer name"]),
+    ("pc name", &["computer name"]),
+    ("updates", &["update"]),
+    ("antivirus", &["windows security", "defender"]),
+    ("programs", &["apps"]),
+    ("uninstall", &["apps & features", "programs and features"]),
+];
+
+/// Terms an item may match for `query`: the query itself, then any synonyms of it
+fn search_terms(query: &str) -> Vec<&str> {
+    let lowered = query.trim().to_lowercase();
+    let synonyms = SYNONYMS
+        .iter()
+        .find(|(word, _)| *word == lowered)
+        .map_or(&[][..], |(_, terms)| *terms);
+    std::iter::once(query).chain(synonyms.iter().copied()).collect()
+}
+
+/// Fuzzy-score an item, known to the user as `alias` if they renamed it, against a search
+/// query and its synonyms, case-insensitively. Returns `None` when the item is not a good
+/// enough match.
+pub fn match_score(matcher: &SkimMatcherV2, item: &SettingsItem, alias: Option<&str>, query: &str) -> Option<i64> {
+    search_terms(query)
+        .into_iter()
+        .filter_map(|term| term_score(matcher, item, alias, term))
+        .max()
+}
+
+/// Names and aliases score in full and keywords a little more. Descriptions are long
+/// enough to fuzzy-match almost any short query by accident, so they count for half
+/// unless they contain the query outright.
+fn term_score(matcher: &SkimMatcherV2, item: &SettingsItem, alias: Option<&str>, query: &str) -> Option<i64> {
+    let name_score = std::iter::once(item.name.as_str())
+        .chain(alias)
+        .filter_map(|name| matcher.fuzzy_match(name, query))
+        .max();
+    let keyword_score = item
+        .keywords
+        .iter()
+        .filter_map(|k| matcher.fuzzy_match(k, query))
+        .max()
+        .map(|score| score * KEYWORD_WEIGHT / 100);
+    let description_score = item.description.as_deref().and_then(|d| {
+        let score = matcher.fuzzy_match(d, query)?;
+        if d.to_lowercase().contains(&query.to_lowercase()) {
+            Some(score)
+        } else {
+            Some(score / 2)
+        }
+    });
+    
+    let score = [name_score, keyword_score, description_score]
+        .into_iter()
+        .flatten()
+        .max()?;
+    
+    let threshold = MIN_SCORE_PER_CHAR * query.chars().count() as i64;
+    (score >= threshold).then_some(score)
+}
+
+pub fn matcher() -> SkimMatcherV2 {
+    SkimMatcherV2::default().ignore_case()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{Category, LaunchType, SETTINGS_ITEMS};
+
+    /// Names of the items matching `query`, best match first
+    fn ranked<'a>(items: &'a [SettingsItem], query: &str) -> Vec<&'a str> {
+        let matcher = matcher();
+        let mut scored: Vec<_> = items
+            .iter()
+            .filter_map(|item| Some((match_score(&matcher, item, None, query)?, item.name.as_str())))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, name)| name).collect()
+    }
+
+    #[test]
+    fn screen_finds_the_resolution_setting() {
+        assert_eq!(ranked(&SETTINGS_ITEMS, "screen").first(), Some(&"Display Resolution"));
+    }
+
+    #[test]
+    fn keyword_hits_outrank_description_hits() {
+        let items = [
+            SettingsItem::new("Alpha", Category::System, LaunchType::MsSettings("alpha".into()))
+                .with_description("Mentions bluetooth in passing"),
+            SettingsItem::new("Beta", Category::System, LaunchType::MsSettings("beta".into()))
+                .with_keywords(vec!["bluetooth".to_string()]),
+        ];
+        assert_eq!(ranked(&items, "bluetooth"), ["Beta", "Alpha"]);
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use once_cell::sync::Lazy;
+use crate::network_editor::{AdapterMedia, NetworkAdapter};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Category {
+    /// Synthetic category listing the user's favorited items from every category
+    Favorites,
     System,
     Network,
     Personalization,
@@ -19,6 +22,7 @@ pub enum Category {
 impl Category {
     pub fn display_name(&self) -> &'static str {
         match self {
+            Category::Favorites => "★ Favorites",
             Category::System => "System & Display",
             Category::Network => "Network & Internet",
             Category::Personalization => "Personalization",
@@ -35,6 +39,7 @@ impl Category {
     
     pub fn all() -> Vec<Category> {
         vec![
+            Category::Favorites,
             Category::System,
             Category::Network,
             Category::Personalization,
@@ -57,6 +62,10 @@ pub enum LaunchType {
     RunDll32(String),
     PowerShell(String),
     Command(String),
+    /// Windows Terminal, optionally in a named profile and running a command instead of the profile's shell
+    WindowsTerminal { profile: Option<String>, command: Option<String> },
+    /// Web page or app deep link opened with its default handler; only http, https and ms- schemes are allowed
+    Url(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +79,8 @@ pub struct SettingsItem {
     pub keywords: Vec<String>,
     pub editor_key: Option<String>,  // Key to identify which editor to use
     pub can_edit_inline: bool,       // Whether this setting can be edited in the TUI
+    pub requires_restart: bool,      // Whether changes only apply after a restart or sign-out
+    pub subcategory: Option<String>, // Group heading within the category, None for the top group
 }
 
 impl SettingsItem {
@@ -88,6 +99,8 @@ impl SettingsItem {
             keywords: vec![],
             editor_key: None,
             can_edit_inline: false,
+            requires_restart: false,
+            subcategory: None,
         }
     }
     
@@ -106,6 +119,16 @@ impl SettingsItem {
         self
     }
     
+    pub fn with_restart(mut self) -> Self {
+        self.requires_restart = true;
+        self
+    }
+    
+    pub fn with_subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.subcategory = Some(subcategory.into());
+        self
+    }
+    
     pub fn with_keywords(mut self, keywords: Vec<String>) -> Self {
         self.keywords = keywords;
         self
@@ -118,68 +141,121 @@ impl SettingsItem {
     }
 }
 
+/// Build the toggle and DNS items for each discovered network adapter
+pub fn network_adapter_items(adapters: &[NetworkAdapter]) -> Vec<SettingsItem> {
+    adapters
+        .iter()
+        .flat_map(|adapter| {
+            let (icon, page) = match adapter.media {
+                AdapterMedia::Wireless => ('📶', "network-wifi"),
+                AdapterMedia::Wired => ('🔌', "network-ethernet"),
+                AdapterMedia::Other => ('🖧', "network-status"),
+            };
+            
+            [
+                SettingsItem::new(format!("{} Adapter", adapter.name), Category::Network, LaunchType::MsSettings(page.into()))
+                    .with_description(format!("Enable or disable {}", adapter.description))
+                    .with_icon(icon)
+                    .with_editor(format!("adapter_toggle:{}", adapter.name))
+                    .with_subcategory(&adapter.name)
+                    .with_admin(),
+                SettingsItem::new(format!("{} DNS Settings", adapter.name), Category::Network, LaunchType::MsSettings(page.into()))
+                    .with_description(format!("Configure DNS servers for {}", adapter.name))
+                    .with_icon('🌐')
+                    .with_editor(format!("adapter_dns:{}", adapter.name))
+                    .with_subcategory(&adapter.name)
+                    .with_admin(),
+            ]
+        })
+        .collect()
+}
+
 pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
     vec![
         // System & Display - Now with inline editing!
         SettingsItem::new("Display Resolution", Category::System, LaunchType::MsSettings("display".into()))
             .with_description("Change screen resolution and refresh rate")
             .with_icon('🖥')
+            .with_subcategory("Display")
             .with_editor("display_resolution"),
             
+        SettingsItem::new("Brightness", Category::System, LaunchType::MsSettings("display".into()))
+            .with_description("Adjust built-in display brightness")
+            .with_icon('🔆')
+            .with_subcategory("Display")
+            .with_editor("brightness"),
+            
+        SettingsItem::new("Display Scaling", Category::System, LaunchType::MsSettings("display".into()))
+            .with_description("Size of text, apps and other items on the primary display")
+            .with_icon('🔍')
+            .with_subcategory("Display")
+            .with_keywords(vec!["dpi".into(), "scale".into(), "zoom".into(), "text size".into()])
+            .with_editor("display_scaling")
+            .with_restart(),
+            
         SettingsItem::new("Sound Settings", Category::System, LaunchType::MsSettings("sound".into()))
             .with_description("Manage audio devices and sound preferences")
             .with_icon('🔊')
+            .with_subcategory("Sound")
             .with_editor("audio_device"),
             
+        SettingsItem::new("Microphone", Category::System, LaunchType::MsSettings("sound".into()))
+            .with_description("Default recording device for apps and calls")
+            .with_icon('🎤')
+            .with_subcategory("Sound")
+            .with_keywords(vec!["input".into(), "recording".into(), "mic".into(), "audio input".into()])
+            .with_editor("default_microphone"),
+            
+        SettingsItem::new("Volume", Category::System, LaunchType::MsSettings("sound".into()))
+            .with_description("Master volume of the default playback device")
+            .with_icon('🔉')
+            .with_subcategory("Sound")
+            .with_editor("master_volume"),
+            
         SettingsItem::new("Power Plans", Category::System, LaunchType::MsSettings("powersleep".into()))
             .with_description("Select and configure power plans")
             .with_icon('🔋')
+            .with_subcategory("Power & Storage")
             .with_editor("power_plan")
             .with_admin(),
             
         SettingsItem::new("Storage", Category::System, LaunchType::MsSettings("storagesense".into()))
             .with_description("View storage usage and manage drives")
-            .with_icon('💾'),
+            .with_icon('💾')
+            .with_subcategory("Power & Storage"),
             
         SettingsItem::new("About This PC", Category::System, LaunchType::MsSettings("about".into()))
             .with_description("View PC specifications and Windows version")
-            .with_icon('ℹ'),
+            .with_icon('ℹ')
+            .with_subcategory("About"),
+            
+        SettingsItem::new("Computer Name", Category::System, LaunchType::MsSettings("about".into()))
+            .with_description("Rename this PC (takes effect after a restart)")
+            .with_icon('🏷')
+            .with_subcategory("About")
+            .with_keywords(vec!["hostname".into(), "rename".into(), "pc name".into()])
+            .with_editor("hostname")
+            .with_admin()
+            .with_restart(),
             
         SettingsItem::new("System Properties", Category::System, LaunchType::ControlPanel("sysdm.cpl".into()))
             .with_description("Advanced system settings and computer name")
             .with_icon('⚙')
+            .with_subcategory("About")
             .with_admin(),
             
-        // Network & Internet - Now with more inline editing!
-        SettingsItem::new("Wi-Fi Adapter", Category::Network, LaunchType::MsSettings("network-wifi".into()))
-            .with_description("Enable or disable Wi-Fi adapter")
-            .with_icon('📶')
-            .with_editor("wifi_adapter_toggle")
-            .with_admin(),
-            
-        SettingsItem::new("Wi-Fi DNS Settings", Category::Network, LaunchType::MsSettings("network-wifi".into()))
-            .with_description("Configure DNS servers for Wi-Fi")
-            .with_icon('🌐')
-            .with_editor("wifi_dns")
-            .with_admin(),
-            
+        // Network & Internet - per-adapter editors are added once the adapters are discovered, see `network_adapter_items`
         SettingsItem::new("Wi-Fi Power Management", Category::Network, LaunchType::MsSettings("network-wifi".into()))
             .with_description("Wi-Fi power saving mode")
             .with_icon('🔋')
             .with_editor("wifi_power_mode")
             .with_admin(),
             
-        SettingsItem::new("Ethernet Adapter", Category::Network, LaunchType::MsSettings("network-ethernet".into()))
-            .with_description("Enable or disable Ethernet adapter")
-            .with_icon('🔌')
-            .with_editor("ethernet_adapter_toggle")
-            .with_admin(),
-            
-        SettingsItem::new("Ethernet DNS Settings", Category::Network, LaunchType::MsSettings("network-ethernet".into()))
-            .with_description("Configure DNS servers for Ethernet")
-            .with_icon('🌐')
-            .with_editor("ethernet_dns")
-            .with_admin(),
+        SettingsItem::new("Bluetooth", Category::Network, LaunchType::MsSettings("bluetooth".into()))
+            .with_description("Turn the Bluetooth radio on or off")
+            .with_icon('🔵')
+            .with_keywords(vec!["wireless".into(), "radio".into(), "devices".into()])
+            .with_editor("bluetooth"),
             
         SettingsItem::new("VPN", Category::Network, LaunchType::MsSettings("network-vpn".into()))
             .with_description("Virtual Private Network connections")
@@ -202,6 +278,12 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Windows colors and transparency effects")
             .with_icon('🎨'),
             
+        SettingsItem::new("Light/Dark Mode", Category::Personalization, LaunchType::MsSettings("personalization-colors".into()))
+            .with_description("Switch apps and the taskbar between light and dark mode")
+            .with_icon('🌓')
+            .with_keywords(vec!["dark mode".into(), "light mode".into(), "theme".into()])
+            .with_editor("app_theme"),
+            
         SettingsItem::new("Themes", Category::Personalization, LaunchType::MsSettings("personalization-themes".into()))
             .with_description("Save and apply theme combinations")
             .with_icon('🎭'),
@@ -229,7 +311,9 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             
         SettingsItem::new("Startup Apps", Category::Apps, LaunchType::MsSettings("startupapps".into()))
             .with_description("Control which apps run at startup")
-            .with_icon('🚀'),
+            .with_icon('🚀')
+            .with_keywords(vec!["autorun".into(), "run at login".into(), "boot".into()])
+            .with_editor("startup_apps"),
             
         SettingsItem::new("Programs and Features", Category::Apps, LaunchType::ControlPanel("appwiz.cpl".into()))
             .with_description("Classic uninstall or change programs")
@@ -252,11 +336,23 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Add family members and other users")
             .with_icon('👨'),
             
+        SettingsItem::new("Microsoft Account", Category::Accounts, LaunchType::Url("https://account.microsoft.com".into()))
+            .with_description("Manage your Microsoft account online")
+            .with_icon('🌐')
+            .with_keywords(vec!["password".into(), "subscriptions".into(), "billing".into()]),
+            
         // Time & Language
         SettingsItem::new("Date & Time", Category::TimeLanguage, LaunchType::MsSettings("dateandtime".into()))
             .with_description("Time zone and date format settings")
             .with_icon('🕐'),
             
+        SettingsItem::new("Time Zone", Category::TimeLanguage, LaunchType::MsSettings("dateandtime".into()))
+            .with_description("Set the system time zone")
+            .with_icon('🌐')
+            .with_keywords(vec!["timezone".into(), "utc".into(), "clock".into()])
+            .with_editor("timezone")
+            .with_admin(),
+            
         SettingsItem::new("Region", Category::TimeLanguage, LaunchType::MsSettings("regionformatting".into()))
             .with_description("Regional formats for dates and numbers")
             .with_icon('🌍'),
@@ -283,9 +379,31 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Text size and display preferences")
             .with_icon('👁'),
             
+        SettingsItem::new("Mouse Speed", Category::Accessibility, LaunchType::MsSettings("mousetouchpad".into()))
+            .with_description("How fast the pointer moves, from 1 to 20")
+            .with_icon('🖱')
+            .with_subcategory("Mouse & Keyboard")
+            .with_keywords(vec!["pointer speed".into(), "sensitivity".into(), "cursor".into()])
+            .with_editor("mouse_speed"),
+            
+        SettingsItem::new("Keyboard Repeat Delay", Category::Accessibility, LaunchType::ControlPanel("keyboard".into()))
+            .with_description("How long a key is held before it repeats, from 0 (short) to 3 (long)")
+            .with_icon('⌨')
+            .with_subcategory("Mouse & Keyboard")
+            .with_keywords(vec!["typing".into(), "key repeat".into()])
+            .with_editor("keyboard_delay"),
+            
+        SettingsItem::new("Keyboard Repeat Rate", Category::Accessibility, LaunchType::ControlPanel("keyboard".into()))
+            .with_description("How fast a held key repeats, from 0 (slow) to 31 (fast)")
+            .with_icon('⌨')
+            .with_subcategory("Mouse & Keyboard")
+            .with_keywords(vec!["typing".into(), "key repeat".into(), "speed".into()])
+            .with_editor("keyboard_speed"),
+            
         SettingsItem::new("Mouse Pointer", Category::Accessibility, LaunchType::MsSettings("easeofaccess-mousepointer".into()))
             .with_description("Pointer size and color")
-            .with_icon('🖱'),
+            .with_icon('🖱')
+            .with_subcategory("Mouse & Keyboard"),
             
         SettingsItem::new("Narrator", Category::Accessibility, LaunchType::MsSettings("easeofaccess-narrator".into()))
             .with_description("Screen reader settings")
@@ -300,6 +418,13 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Antivirus and threat protection")
             .with_icon('🛡'),
             
+        SettingsItem::new("Firewall", Category::Privacy, LaunchType::ControlPanel("firewall.cpl".into()))
+            .with_description("Turn Windows Defender Firewall on or off for the current network profile")
+            .with_icon('🧱')
+            .with_keywords(vec!["defender".into(), "security".into(), "netsh".into()])
+            .with_editor("firewall")
+            .with_admin(),
+            
         SettingsItem::new("Camera Privacy", Category::Privacy, LaunchType::MsSettings("privacy-webcam".into()))
             .with_description("Control app access to camera")
             .with_icon('📷'),
@@ -317,6 +442,13 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Check and install Windows updates")
             .with_icon('🔄'),
             
+        SettingsItem::new("Pause Updates", Category::Update, LaunchType::MsSettings("windowsupdate".into()))
+            .with_description("Pause Windows Update for 7 days, or resume it")
+            .with_icon('⏸')
+            .with_keywords(vec!["defer".into(), "postpone".into()])
+            .with_editor("pause_updates")
+            .with_admin(),
+            
         SettingsItem::new("Update History", Category::Update, LaunchType::MsSettings("windowsupdate-history".into()))
             .with_description("View installed updates")
             .with_icon('📜'),
@@ -325,6 +457,11 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_description("Update delivery and installation options")
             .with_icon('⚡'),
             
+        SettingsItem::new("Release Health", Category::Update, LaunchType::Url("https://learn.microsoft.com/windows/release-health/".into()))
+            .with_description("Known issues and release notes for Windows updates")
+            .with_icon('🩺')
+            .with_keywords(vec!["known issues".into(), "release notes".into()]),
+            
         // Control Panel (Classic)
         SettingsItem::new("Device Manager", Category::ControlPanel, LaunchType::RunDll32("devmgr.dll DeviceManager_Execute".into()))
             .with_description("Manage hardware devices and drivers")
@@ -336,6 +473,12 @@ pub static SETTINGS_ITEMS: Lazy<Vec<SettingsItem>> = Lazy::new(|| {
             .with_icon('🔨')
             .with_admin(),
             
+        SettingsItem::new("Administrator Terminal", Category::ControlPanel, LaunchType::WindowsTerminal { profile: None, command: None })
+            .with_description("Windows Terminal running as administrator")
+            .with_icon('💻')
+            .with_keywords(vec!["shell".into(), "console".into(), "wt".into(), "elevated".into()])
+            .with_admin(),
+            
         SettingsItem::new("Power Options", Category::ControlPanel, LaunchType::ControlPanel("powercfg.cpl".into()))
             .with_description("Classic power plan settings")
             .with_icon('⚡'),
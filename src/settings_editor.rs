@@ -1,12 +1,58 @@
 use anyhow::{Result, Context};
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
-use std::process::Command;
+use std::sync::Arc;
+use std::process::{Command, Output};
+use std::time::Duration;
 use windows::{
     core::PCWSTR,
+    Win32::Foundation::HWND,
     Win32::Graphics::Gdi::*,
+    Win32::Media::Audio::{eConsole, eRender},
+    Win32::Media::Audio::Endpoints::IAudioEndpointVolume,
+    Win32::System::Com::{CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED},
 };
 use crate::network_editor::*;
+use crate::display_editor::*;
+use crate::personalization_editor::*;
+use crate::system_editor::*;
+use crate::input_editor::*;
+use crate::apps_editor::*;
+use crate::audio_editor::*;
+use crate::exec;
+
+/// Running editor commands to completion and treating a non-zero exit as failure,
+/// since tools like `netsh` and `powercfg` report problems only through their exit code
+pub trait CommandExt {
+    /// Like `Command::output`, but fails with `context` and the command's error text
+    /// when it can't be started or exits unsuccessfully
+    fn checked_output(&mut self, context: &str) -> Result<Output>;
+}
+
+impl CommandExt for Command {
+    fn checked_output(&mut self, context: &str) -> Result<Output> {
+        let output = exec::run_logged(self)
+            .map_err(|e| anyhow::anyhow!("{}: {}", context, e))?;
+        if !output.status.success() {
+            // netsh and powercfg write their errors to stdout rather than stderr
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let message = if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() };
+            let code = output
+                .status
+                .code()
+                .map(|code| format!("exit code {}", code))
+                .unwrap_or_else(|| "terminated".to_string());
+            if message.is_empty() {
+                anyhow::bail!("{} ({})", context, code);
+            }
+            anyhow::bail!("{}: {} ({})", context, message, code);
+        }
+        Ok(output)
+    }
+}
 
 /// Represents different types of setting values
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -16,7 +62,9 @@ pub enum SettingValue {
     Integer(i64),
     Float(f64),
     Selection(String), // For dropdown selections
-    Resolution { width: u32, height: u32 },
+    Resolution { width: u32, height: u32, refresh: u32 },
+    /// For multi-select lists: whether each option, by id, is switched on
+    Flags(BTreeMap<String, bool>),
     Custom(serde_json::Value),
 }
 
@@ -28,12 +76,48 @@ impl fmt::Display for SettingValue {
             SettingValue::Integer(i) => write!(f, "{}", i),
             SettingValue::Float(fl) => write!(f, "{:.2}", fl),
             SettingValue::Selection(s) => write!(f, "{}", s),
-            SettingValue::Resolution { width, height } => write!(f, "{}x{}", width, height),
+            SettingValue::Resolution { width, height, refresh } => write!(f, "{}x{} @ {}Hz", width, height, refresh),
+            SettingValue::Flags(flags) => write!(
+                f,
+                "{} of {} enabled",
+                flags.values().filter(|enabled| **enabled).count(),
+                flags.len()
+            ),
             SettingValue::Custom(v) => write!(f, "{}", v),
         }
     }
 }
 
+impl SettingValue {
+    /// Like `to_string`, but numbers follow the editor's `ValueFormat` when it has one
+    pub fn display_with(&self, format: Option<ValueFormat>) -> String {
+        match (self, format) {
+            (SettingValue::Integer(i), Some(format)) => format!("{}{}", i, format.unit),
+            (SettingValue::Float(fl), Some(format)) => format.number(*fl),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// How an editor's numbers are shown, e.g. brightness as `50%` rather than `50.00`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueFormat {
+    /// Digits after the decimal point
+    pub precision: usize,
+    /// Written right after the number, e.g. "%" or " ms"
+    pub unit: &'static str,
+}
+
+impl ValueFormat {
+    pub const fn new(precision: usize, unit: &'static str) -> Self {
+        Self { precision, unit }
+    }
+    
+    pub fn number(&self, value: f64) -> String {
+        format!("{:.*}{}", self.precision, value, self.unit)
+    }
+}
+
 /// Represents an option for a setting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SettingOption {
@@ -51,6 +135,8 @@ pub enum EditorType {
     TextInput { multiline: bool },
     NumberInput { min: Option<i64>, max: Option<i64> },
     ResolutionPicker,
+    /// A checklist of options switched on and off individually, valued as `SettingValue::Flags`
+    MultiSelect,
     Custom,
 }
 
@@ -74,8 +160,87 @@ pub trait SettingEditor: Send + Sync + fmt::Debug {
     /// Get the appropriate editor type for UI
     fn get_editor_type(&self) -> EditorType;
     
-    /// Check if setting requires admin privileges
+    /// Check if setting requires admin privileges. Without them the underlying commands
+    /// fail with access denied, so callers refuse to save before trying.
     fn requires_admin(&self) -> bool;
+    
+    /// What else the user must do for an applied change to fully take effect, like restarting
+    fn apply_note(&self) -> Option<&'static str> {
+        None
+    }
+    
+    /// Whether applying a value can cut off the user (e.g. blank the screen or drop the network)
+    fn is_destructive(&self) -> bool {
+        false
+    }
+    
+    /// If set, an applied change reverts after this long unless the user keeps it
+    fn revert_timeout(&self) -> Option<Duration> {
+        None
+    }
+    
+    /// Whether an applied change can be undone by writing the previous value back
+    fn supports_undo(&self) -> bool {
+        true
+    }
+    
+    /// Precision and unit for showing `Integer` and `Float` values, `None` for the plain `Display`
+    fn value_format(&self) -> Option<ValueFormat> {
+        None
+    }
+    
+    /// How far Shift+Left/Right moves a slider stepping by `step`, a tenth of a step
+    /// unless the editor knows a better size
+    fn fine_step(&self, step: f64) -> f64 {
+        step * 0.1
+    }
+    
+    /// Whether the current value can be read, so the list and detail pane know to not ask for it
+    fn can_read(&self) -> bool {
+        true
+    }
+    
+    /// Whether `set_value` is supported; editors that can't write open as a read-only view
+    fn can_write(&self) -> bool {
+        true
+    }
+    
+    /// Whether an applied change only takes full effect after a restart or sign-out
+    fn requires_restart(&self) -> bool {
+        false
+    }
+    
+    /// Whether reading and saving both work, `false` for editors whose backend is only
+    /// partly written so the list can warn before the user gets as far as saving
+    fn is_fully_implemented(&self) -> bool {
+        self.can_read() && self.can_write()
+    }
+    
+    /// Capture the full display mode before a change so a revert restores it exactly
+    fn capture_display_mode(&self) -> Result<Option<DisplayModeSnapshot>> {
+        Ok(None)
+    }
+    
+    /// Editor to switch to when an option asks for free-form entry instead of a preset
+    fn custom_entry_editor(&self, _value: &SettingValue) -> Option<EditorType> {
+        None
+    }
+    
+    /// Editor bound to the target (e.g. a display) chosen from this editor's options,
+    /// for settings that first ask which device to operate on
+    fn select_target(&self, _value: &SettingValue) -> Option<Box<dyn SettingEditor>> {
+        None
+    }
+    
+    /// Why a chosen value may not work even though it can be saved, from a check too slow
+    /// for the UI thread (e.g. probing a server), so it runs in the background
+    fn check_value(&self, _value: &SettingValue) -> Option<String> {
+        None
+    }
+    
+    /// Drop anything read from the system and kept for later, like the modes a display
+    /// supports, so it is read again on next use
+    fn refresh_cache(&mut self) {}
 }
 
 impl Clone for Box<dyn SettingEditor> {
@@ -84,24 +249,201 @@ impl Clone for Box<dyn SettingEditor> {
     }
 }
 
-/// Display settings editor implementation
+/// Label for `value` as the user would recognize it: the matching option's label, e.g. a
+/// power plan's name over its GUID, or else the value in the editor's format
+pub fn option_label(editor: &dyn SettingEditor, options: &[SettingOption], value: &SettingValue) -> String {
+    options
+        .iter()
+        .find(|option| &option.value == value)
+        .map_or_else(|| value.display_with(editor.value_format()), |option| option.label.clone())
+}
+
+/// An attached display device as reported by `EnumDisplayDevicesW`
+#[derive(Debug, Clone)]
+pub struct DisplayDevice {
+    pub device_name: String,
+    pub monitor_name: String,
+    pub is_primary: bool,
+}
+
+/// Enumerate displays that are attached to the desktop, skipping disconnected adapters
+pub fn get_display_devices() -> Result<Vec<DisplayDevice>> {
+    let mut devices = Vec::new();
+    
+    let mut i = 0;
+    unsafe {
+        loop {
+            let mut adapter = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            if !EnumDisplayDevicesW(PCWSTR::null(), i, &mut adapter, 0).as_bool() {
+                break;
+            }
+            i += 1;
+            
+            if adapter.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP == 0 {
+                continue;
+            }
+            
+            // Query the monitor attached to this adapter output for a friendlier name
+            let mut monitor = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            let monitor_name = if EnumDisplayDevicesW(PCWSTR(adapter.DeviceName.as_ptr()), 0, &mut monitor, 0).as_bool() {
+                from_wide(&monitor.DeviceString)
+            } else {
+                from_wide(&adapter.DeviceString)
+            };
+            
+            devices.push(DisplayDevice {
+                device_name: from_wide(&adapter.DeviceName),
+                monitor_name,
+                is_primary: adapter.StateFlags & DISPLAY_DEVICE_PRIMARY_DEVICE != 0,
+            });
+        }
+    }
+    
+    if devices.is_empty() {
+        anyhow::bail!("No attached displays found");
+    }
+    
+    Ok(devices)
+}
+
+/// Join the calling thread to the multithreaded COM apartment before using COM or WinRT.
+/// Editors run on whichever worker thread loads them, and a thread that already joined
+/// just gets a status code back, so the result is ignored.
+pub fn init_com() {
+    let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+}
+
+pub fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
+
+pub fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// A display's complete `DEVMODEW` (position, bit depth, orientation, ...) at a point in time
+#[derive(Clone)]
+pub struct DisplayModeSnapshot {
+    device_name: Option<String>,
+    mode: DEVMODEW,
+}
+
+impl fmt::Debug for DisplayModeSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisplayModeSnapshot")
+            .field("device_name", &self.device_name)
+            .field("width", &self.mode.dmPelsWidth)
+            .field("height", &self.mode.dmPelsHeight)
+            .field("refresh", &self.mode.dmDisplayFrequency)
+            .finish()
+    }
+}
+
+impl DisplayModeSnapshot {
+    /// Re-apply the captured mode
+    pub fn restore(&self) -> Result<()> {
+        if exec::is_dry_run() {
+            exec::log_dry_run(&format!(
+                "restore display mode {} × {} @ {}Hz",
+                self.mode.dmPelsWidth, self.mode.dmPelsHeight, self.mode.dmDisplayFrequency
+            ));
+            return Ok(());
+        }
+        
+        let device = self.device_name.as_deref().map(to_wide);
+        let device_ptr = device.as_ref().map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr()));
+        
+        unsafe {
+            let result = ChangeDisplaySettingsExW(device_ptr, Some(&self.mode), HWND(0), CDS_TYPE(0), None);
+            if result != DISP_CHANGE_SUCCESSFUL {
+                anyhow::bail!("Failed to restore display settings: {:?}", result);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Display settings editor implementation.
+///
+/// Without a device name the editor asks which display to operate on, then
+/// hands over to an editor bound to that display via `select_target`.
 #[derive(Debug, Clone)]
-pub struct DisplaySettingsEditor;
+pub struct DisplaySettingsEditor {
+    device_name: Option<String>,
+    /// Modes the targeted display supports, enumerated on first use since listing them
+    /// queries the driver. Shared between clones, so the copy loading the options and the
+    /// one validating the choice enumerate them only once per edit session.
+    modes: Arc<OnceCell<Vec<(u32, u32, u32)>>>,
+    /// Lists a display's modes, replaced in tests to keep the driver out of them
+    enumerate: fn(&str) -> Vec<(u32, u32, u32)>,
+}
+
+impl Default for DisplaySettingsEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DisplaySettingsEditor {
     pub fn new() -> Self {
-        Self
+        Self { device_name: None, modes: Arc::default(), enumerate: Self::enumerate_modes }
+    }
+    
+    pub fn for_device(device_name: String) -> Self {
+        Self { device_name: Some(device_name), modes: Arc::default(), enumerate: Self::enumerate_modes }
     }
     
-    fn get_display_modes(&self) -> Result<Vec<(u32, u32, u32)>> {
+    /// An editor whose modes are already known, so tests never query a driver
+    #[cfg(test)]
+    fn with_modes(device_name: Option<&str>, modes: Vec<(u32, u32, u32)>) -> Self {
+        Self {
+            device_name: device_name.map(str::to_string),
+            modes: Arc::new(OnceCell::with_value(modes)),
+            enumerate: |_| unreachable!("modes were given up front"),
+        }
+    }
+    
+    /// An editor listing its display's modes with `enumerate` instead of the driver
+    #[cfg(test)]
+    fn with_enumerator(device_name: &str, enumerate: fn(&str) -> Vec<(u32, u32, u32)>) -> Self {
+        Self { enumerate, ..Self::for_device(device_name.to_string()) }
+    }
+    
+    fn modes(&self) -> &[(u32, u32, u32)] {
+        match &self.device_name {
+            Some(device_name) => self.modes.get_or_init(|| (self.enumerate)(device_name)),
+            None => &[],
+        }
+    }
+    
+    /// Forget the enumerated modes so they are listed again on next use, e.g. after
+    /// another monitor was plugged into the display's port
+    pub fn refresh_modes(&mut self) {
+        self.modes = Arc::default();
+    }
+    
+    fn device_name_wide(&self) -> Option<Vec<u16>> {
+        self.device_name.as_deref().map(to_wide)
+    }
+    
+    fn enumerate_modes(device_name: &str) -> Vec<(u32, u32, u32)> {
         let mut modes = Vec::new();
         let mut dev_mode = DEVMODEW::default();
         dev_mode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+        let device = to_wide(device_name);
+        let device_ptr = PCWSTR(device.as_ptr());
         
         let mut i = 0;
         unsafe {
             loop {
-                let result = EnumDisplaySettingsW(PCWSTR::null(), ENUM_DISPLAY_SETTINGS_MODE(i), &mut dev_mode);
+                let result = EnumDisplaySettingsW(device_ptr, ENUM_DISPLAY_SETTINGS_MODE(i), &mut dev_mode);
                 if !result.as_bool() {
                     break;
                 }
@@ -115,27 +457,36 @@ impl DisplaySettingsEditor {
             }
         }
         
-        // Sort by resolution (width * height) descending
+        // Group by aspect ratio (matching the picker layout), then largest
+        // resolution first, then highest refresh rate first
         modes.sort_by(|a, b| {
-            let area_a = a.0 * a.1;
-            let area_b = b.0 * b.1;
-            area_b.cmp(&area_a)
+            aspect_ratio(a.0, a.1)
+                .cmp(&aspect_ratio(b.0, b.1))
+                .then((b.0 * b.1).cmp(&(a.0 * a.1)))
+                .then(b.2.cmp(&a.2))
         });
         
-        Ok(modes)
+        modes
     }
     
-    fn get_current_display_mode(&self) -> Result<(u32, u32, u32)> {
+    fn get_current_devmode(&self) -> Result<DEVMODEW> {
         let mut dev_mode = DEVMODEW::default();
         dev_mode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+        let device = self.device_name_wide();
+        let device_ptr = device.as_ref().map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr()));
         
         unsafe {
-            let result = EnumDisplaySettingsW(PCWSTR::null(), ENUM_CURRENT_SETTINGS, &mut dev_mode);
+            let result = EnumDisplaySettingsW(device_ptr, ENUM_CURRENT_SETTINGS, &mut dev_mode);
             if !result.as_bool() {
                 anyhow::bail!("Failed to get current display settings");
             }
         }
         
+        Ok(dev_mode)
+    }
+    
+    fn get_current_display_mode(&self) -> Result<(u32, u32, u32)> {
+        let dev_mode = self.get_current_devmode()?;
         Ok((dev_mode.dmPelsWidth, dev_mode.dmPelsHeight, dev_mode.dmDisplayFrequency))
     }
 }
@@ -146,76 +497,138 @@ impl SettingEditor for DisplaySettingsEditor {
     }
 
     fn get_current_value(&self) -> Result<SettingValue> {
-        let (width, height, _) = self.get_current_display_mode()?;
-        Ok(SettingValue::Resolution { width, height })
+        if self.device_name.is_none() {
+            let devices = get_display_devices()?;
+            let primary = devices.iter().find(|d| d.is_primary).unwrap_or(&devices[0]);
+            return Ok(SettingValue::Selection(primary.device_name.clone()));
+        }
+        
+        let (width, height, refresh) = self.get_current_display_mode()?;
+        Ok(SettingValue::Resolution { width, height, refresh })
     }
     
     fn get_available_options(&self) -> Result<Vec<SettingOption>> {
-        let modes = self.get_display_modes()?;
-        let mut options = Vec::new();
-        
-        // Group by resolution, showing refresh rates
-        let mut seen_resolutions = std::collections::HashSet::new();
-        
-        for (width, height, refresh) in modes {
-            let res_key = (width, height);
-            if seen_resolutions.insert(res_key) {
-                options.push(SettingOption {
-                    label: format!("{} × {}", width, height),
-                    value: SettingValue::Resolution { width, height },
-                    description: Some(format!("{}Hz available", refresh)),
-                });
-            }
+        if self.device_name.is_none() {
+            return Ok(get_display_devices()?.into_iter().map(|device| {
+                SettingOption {
+                    label: device.monitor_name,
+                    value: SettingValue::Selection(device.device_name.clone()),
+                    description: Some(if device.is_primary {
+                        format!("{} (primary)", device.device_name)
+                    } else {
+                        device.device_name
+                    }),
+                }
+            }).collect());
         }
         
-        Ok(options)
+        // One option per resolution and refresh rate combination
+        Ok(self.modes().iter().copied().map(|(width, height, refresh)| {
+            SettingOption {
+                label: format!("{} × {} @ {}Hz", width, height, refresh),
+                value: SettingValue::Resolution { width, height, refresh },
+                description: None,
+            }
+        }).collect())
     }
     
     fn set_value(&self, value: SettingValue) -> Result<()> {
-        if let SettingValue::Resolution { width, height } = value {
+        if let SettingValue::Resolution { width, height, refresh } = value {
             let mut dev_mode = DEVMODEW::default();
             dev_mode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
             dev_mode.dmPelsWidth = width;
             dev_mode.dmPelsHeight = height;
-            dev_mode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT;
+            dev_mode.dmDisplayFrequency = refresh;
+            dev_mode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY;
+            let device = self.device_name_wide();
+            let device_ptr = device.as_ref().map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr()));
             
             unsafe {
-                let result = ChangeDisplaySettingsW(Some(&dev_mode), CDS_TEST);
+                let result = ChangeDisplaySettingsExW(device_ptr, Some(&dev_mode), HWND(0), CDS_TEST, None);
                 if result != DISP_CHANGE_SUCCESSFUL {
                     anyhow::bail!("Display mode test failed: {:?}", result);
                 }
                 
-                let result = ChangeDisplaySettingsW(Some(&dev_mode), CDS_TYPE(0));
+                let result = ChangeDisplaySettingsExW(device_ptr, Some(&dev_mode), HWND(0), CDS_TYPE(0), None);
                 if result != DISP_CHANGE_SUCCESSFUL {
                     anyhow::bail!("Failed to change display settings: {:?}", result);
                 }
             }
             Ok(())
         } else {
-            anyhow::bail!("Invalid value type for display settings")
+            anyhow::bail!("Select a display first")
         }
     }
     
     fn validate_value(&self, value: &SettingValue) -> Result<bool> {
-        if let SettingValue::Resolution { width, height } = value {
-            let modes = self.get_display_modes()?;
-            Ok(modes.iter().any(|(w, h, _)| w == width && h == height))
+        if let SettingValue::Resolution { width, height, refresh } = value {
+            // Modes belong to the display this editor targets, so another monitor's list never applies
+            if self.device_name.is_none() {
+                return Ok(false);
+            }
+            Ok(self.modes().contains(&(*width, *height, *refresh)))
         } else {
             Ok(false)
         }
     }
     
+    fn refresh_cache(&mut self) {
+        self.refresh_modes();
+    }
+    
     fn get_editor_type(&self) -> EditorType {
-        EditorType::ResolutionPicker
+        if self.device_name.is_none() {
+            EditorType::Dropdown
+        } else {
+            EditorType::ResolutionPicker
+        }
     }
     
     fn requires_admin(&self) -> bool {
         false
     }
+    
+    fn is_destructive(&self) -> bool {
+        self.device_name.is_some()
+    }
+    
+    fn revert_timeout(&self) -> Option<Duration> {
+        // Like Windows, give the user a chance to back out of a mode with no signal
+        self.device_name.as_ref().map(|_| Duration::from_secs(15))
+    }
+    
+    fn capture_display_mode(&self) -> Result<Option<DisplayModeSnapshot>> {
+        if self.device_name.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(DisplayModeSnapshot {
+            device_name: self.device_name.clone(),
+            mode: self.get_current_devmode()?,
+        }))
+    }
+    
+    fn select_target(&self, value: &SettingValue) -> Option<Box<dyn SettingEditor>> {
+        match (&self.device_name, value) {
+            (None, SettingValue::Selection(device_name)) => {
+                Some(Box::new(DisplaySettingsEditor::for_device(device_name.clone())))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reduced aspect ratio label for a resolution, e.g. "16:9"
+pub fn aspect_ratio(width: u32, height: u32) -> String {
+    let divisor = gcd(width, height).max(1);
+    format!("{}:{}", width / divisor, height / divisor)
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
 }
 
 /// Power plan settings editor
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PowerPlanEditor;
 
 impl PowerPlanEditor {
@@ -226,68 +639,84 @@ impl PowerPlanEditor {
     fn get_power_plans(&self) -> Result<Vec<(String, String)>> {
         let output = Command::new("powercfg")
             .args(&["/list"])
-            .output()
-            .context("Failed to execute powercfg")?;
+            .checked_output("Failed to execute powercfg")?;
         
         let output_str = String::from_utf8_lossy(&output.stdout);
-        let mut plans = Vec::new();
-        
-        for line in output_str.lines() {
-            if line.contains("GUID:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if let Some(guid_pos) = parts.iter().position(|&x| x == "GUID:") {
-                    if let Some(guid) = parts.get(guid_pos + 1) {
-                        let name = parts[guid_pos + 2..].join(" ")
-                            .trim_start_matches('(')
-                            .trim_end_matches(')')
-                            .trim_end_matches('*')
-                            .trim()
-                            .to_string();
-                        plans.push((guid.to_string(), name));
-                    }
-                }
-            }
-        }
-        
-        Ok(plans)
+        Ok(output_str
+            .lines()
+            .filter_map(parse_power_scheme)
+            .map(|scheme| (scheme.guid, scheme.name))
+            .collect())
     }
     
     fn get_active_plan(&self) -> Result<String> {
         let output = Command::new("powercfg")
             .args(&["/getactivescheme"])
-            .output()
-            .context("Failed to get active power scheme")?;
+            .checked_output("Failed to get active power scheme")?;
         
         let output_str = String::from_utf8_lossy(&output.stdout);
-        for line in output_str.lines() {
-            if line.contains("GUID:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if let Some(guid_pos) = parts.iter().position(|&x| x == "GUID:") {
-                    if let Some(guid) = parts.get(guid_pos + 1) {
-                        return Ok(guid.to_string());
-                    }
-                }
-            }
-        }
-        
-        anyhow::bail!("Could not determine active power plan")
+        output_str
+            .lines()
+            .find_map(parse_power_scheme)
+            .map(|scheme| scheme.guid)
+            .context("Could not determine active power plan")
     }
 }
 
+/// A power scheme as listed by `powercfg /list` or `/getactivescheme`
+#[derive(Debug, Clone)]
+struct PowerScheme {
+    guid: String,
+    name: String,
+}
+
+/// Parse a line like `Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *`.
+///
+/// The label before the GUID is translated on non-English installs, so lines are
+/// recognized by the GUID itself. The name runs from the first `(` after it to the last
+/// `)`, which keeps parentheses inside custom plan names, and the `*` marking the active
+/// plan is dropped.
+fn parse_power_scheme(line: &str) -> Option<PowerScheme> {
+    let (start, guid) = find_guid(line)?;
+    let rest = line[start + guid.len()..].trim().trim_end_matches('*').trim_end();
+    let name = match (rest.find('('), rest.rfind(')')) {
+        (Some(open), Some(close)) if open < close => rest[open + 1..close].trim(),
+        _ => rest,
+    };
+    Some(PowerScheme {
+        guid: guid.to_string(),
+        // Unnamed schemes still need a label to pick them by
+        name: if name.is_empty() { guid.to_string() } else { name.to_string() },
+    })
+}
+
+/// First GUID in `text` in its 8-4-4-4-12 hex digit form, with the byte offset it starts at
+fn find_guid(text: &str) -> Option<(usize, &str)> {
+    const LEN: usize = 36;
+    const DASHES: [usize; 4] = [8, 13, 18, 23];
+    let bytes = text.as_bytes();
+    (0..=bytes.len().checked_sub(LEN)?)
+        .find(|&start| {
+            bytes[start..start + LEN].iter().enumerate().all(|(i, byte)| {
+                if DASHES.contains(&i) {
+                    *byte == b'-'
+                } else {
+                    byte.is_ascii_hexdigit()
+                }
+            })
+        })
+        // Every matched byte is ASCII, so both ends fall on character boundaries
+        .map(|start| (start, &text[start..start + LEN]))
+}
+
 impl SettingEditor for PowerPlanEditor {
     fn clone_box(&self) -> Box<dyn SettingEditor> {
         Box::new(self.clone())
     }
 
     fn get_current_value(&self) -> Result<SettingValue> {
-        let active_guid = self.get_active_plan()?;
-        let plans = self.get_power_plans()?;
-        
-        if let Some((_, name)) = plans.iter().find(|(guid, _)| guid == &active_guid) {
-            Ok(SettingValue::Selection(name.clone()))
-        } else {
-            Ok(SettingValue::Selection("Unknown".to_string()))
-        }
+        // The GUID matches the option values; the UI shows the plan name from the option label
+        Ok(SettingValue::Selection(self.get_active_plan()?))
     }
     
     fn get_available_options(&self) -> Result<Vec<SettingOption>> {
@@ -305,8 +734,7 @@ impl SettingEditor for PowerPlanEditor {
         if let SettingValue::Selection(guid) = value {
             Command::new("powercfg")
                 .args(&["/setactive", &guid])
-                .output()
-                .context("Failed to set active power scheme")?;
+                .checked_output("Failed to set active power scheme")?;
             Ok(())
         } else {
             anyhow::bail!("Invalid value type for power plan")
@@ -331,94 +759,348 @@ impl SettingEditor for PowerPlanEditor {
     }
 }
 
-/// Default audio device editor
-#[derive(Debug, Clone)]
-pub struct AudioDeviceEditor;
+/// Master volume editor for the default playback device
+#[derive(Debug, Clone, Default)]
+pub struct VolumeEditor;
 
-impl AudioDeviceEditor {
+impl VolumeEditor {
     pub fn new() -> Self {
         Self
     }
     
-    fn get_audio_devices(&self) -> Result<Vec<(String, String)>> {
-        // This is a simplified version - in practice, you'd use Windows Core Audio APIs
-        // For now, we'll use PowerShell
-        let script = r#"
-            Get-CimInstance Win32_SoundDevice | Select-Object Name, DeviceID | ConvertTo-Json
-        "#;
-        
-        let output = Command::new("powershell")
-            .args(&["-NoProfile", "-Command", script])
-            .output()
-            .context("Failed to get audio devices")?;
-        
-        let devices: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)
-            .context("Failed to parse audio devices")?;
-        
-        Ok(devices.iter()
-            .filter_map(|d| {
-                let name = d["Name"].as_str()?;
-                let id = d["DeviceID"].as_str()?;
-                Some((id.to_string(), name.to_string()))
-            })
-            .collect())
+    fn get_endpoint_volume(&self) -> Result<IAudioEndpointVolume> {
+        unsafe {
+            let device = device_enumerator()?
+                .GetDefaultAudioEndpoint(eRender, eConsole)
+                .context("No default playback device found")?;
+            device
+                .Activate::<IAudioEndpointVolume>(CLSCTX_ALL, None)
+                .context("Failed to access playback device volume")
+        }
     }
 }
 
-impl SettingEditor for AudioDeviceEditor {
+impl SettingEditor for VolumeEditor {
     fn clone_box(&self) -> Box<dyn SettingEditor> {
         Box::new(self.clone())
     }
 
     fn get_current_value(&self) -> Result<SettingValue> {
-        // Simplified - would need Core Audio API for actual implementation
-        Ok(SettingValue::Selection("Default Device".to_string()))
+        let endpoint = self.get_endpoint_volume()?;
+        let level = unsafe { endpoint.GetMasterVolumeLevelScalar() }
+            .context("Failed to read master volume")?;
+        Ok(SettingValue::Float((level as f64 * 100.0).round()))
     }
     
     fn get_available_options(&self) -> Result<Vec<SettingOption>> {
-        let devices = self.get_audio_devices()?;
-        Ok(devices.into_iter().map(|(id, name)| {
-            SettingOption {
-                label: name,
-                value: SettingValue::Selection(id),
-                description: None,
-            }
-        }).collect())
+        Ok(vec![])
     }
     
-    fn set_value(&self, _value: SettingValue) -> Result<()> {
-        // Would require Core Audio API implementation
-        anyhow::bail!("Audio device switching not yet implemented")
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Float(level) = value {
+            let endpoint = self.get_endpoint_volume()?;
+            let scalar = (level.clamp(0.0, 100.0) / 100.0) as f32;
+            unsafe { endpoint.SetMasterVolumeLevelScalar(scalar, std::ptr::null()) }
+                .context("Failed to set master volume")?;
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for master volume")
+        }
     }
     
     fn validate_value(&self, value: &SettingValue) -> Result<bool> {
-        if let SettingValue::Selection(_) = value {
-            Ok(true)
+        if let SettingValue::Float(level) = value {
+            Ok((0.0..=100.0).contains(level))
         } else {
             Ok(false)
         }
     }
     
     fn get_editor_type(&self) -> EditorType {
-        EditorType::Dropdown
+        EditorType::Slider { min: 0.0, max: 100.0, step: 5.0 }
     }
     
     fn requires_admin(&self) -> bool {
         false
     }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        Some(ValueFormat::new(0, "%"))
+    }
+    
+    /// Windows shows the volume in whole percent, so fine steps move by one
+    fn fine_step(&self, _step: f64) -> f64 {
+        1.0
+    }
+}
+
+/// Wraps an editor in `--dry-run` mode so reads still happen but nothing is ever written,
+/// recording what would have been set instead
+#[derive(Debug, Clone)]
+pub struct DryRunEditor {
+    key: String,
+    inner: Box<dyn SettingEditor>,
+}
+
+impl SettingEditor for DryRunEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        self.inner.get_current_value()
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        self.inner.get_available_options()
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        exec::log_dry_run(&format!("set {} to {}", self.key, value));
+        Ok(())
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        self.inner.validate_value(value)
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        self.inner.get_editor_type()
+    }
+    
+    fn requires_admin(&self) -> bool {
+        self.inner.requires_admin()
+    }
+    
+    fn apply_note(&self) -> Option<&'static str> {
+        self.inner.apply_note()
+    }
+    
+    fn is_destructive(&self) -> bool {
+        self.inner.is_destructive()
+    }
+    
+    fn revert_timeout(&self) -> Option<Duration> {
+        self.inner.revert_timeout()
+    }
+    
+    fn supports_undo(&self) -> bool {
+        self.inner.supports_undo()
+    }
+    
+    fn value_format(&self) -> Option<ValueFormat> {
+        self.inner.value_format()
+    }
+    
+    fn fine_step(&self, step: f64) -> f64 {
+        self.inner.fine_step(step)
+    }
+    
+    fn can_read(&self) -> bool {
+        self.inner.can_read()
+    }
+    
+    fn can_write(&self) -> bool {
+        self.inner.can_write()
+    }
+    
+    fn requires_restart(&self) -> bool {
+        self.inner.requires_restart()
+    }
+    
+    fn is_fully_implemented(&self) -> bool {
+        self.inner.is_fully_implemented()
+    }
+    
+    fn capture_display_mode(&self) -> Result<Option<DisplayModeSnapshot>> {
+        self.inner.capture_display_mode()
+    }
+    
+    fn custom_entry_editor(&self, value: &SettingValue) -> Option<EditorType> {
+        self.inner.custom_entry_editor(value)
+    }
+    
+    fn select_target(&self, value: &SettingValue) -> Option<Box<dyn SettingEditor>> {
+        let inner = self.inner.select_target(value)?;
+        Some(Box::new(DryRunEditor { key: self.key.clone(), inner }))
+    }
+    
+    fn check_value(&self, value: &SettingValue) -> Option<String> {
+        self.inner.check_value(value)
+    }
+    
+    fn refresh_cache(&mut self) {
+        self.inner.refresh_cache();
+    }
 }
 
 /// Factory function to create appropriate editor for a setting
 pub fn create_editor(setting_type: &str) -> Option<Box<dyn SettingEditor>> {
+    let editor = create_system_editor(setting_type)?;
+    if exec::is_dry_run() {
+        return Some(Box::new(DryRunEditor {
+            key: setting_type.to_string(),
+            inner: editor,
+        }));
+    }
+    Some(editor)
+}
+
+/// Editor that reads and writes the actual system setting for `setting_type`
+fn create_system_editor(setting_type: &str) -> Option<Box<dyn SettingEditor>> {
     match setting_type {
         "display_resolution" => Some(Box::new(DisplaySettingsEditor::new())),
         "power_plan" => Some(Box::new(PowerPlanEditor::new())),
         "audio_device" => Some(Box::new(AudioDeviceEditor::new())),
-        "wifi_adapter_toggle" => Some(Box::new(NetworkAdapterToggleEditor::new("Wi-Fi".to_string()))),
-        "ethernet_adapter_toggle" => Some(Box::new(NetworkAdapterToggleEditor::new("Ethernet".to_string()))),
-        "wifi_dns" => Some(Box::new(DNSServerEditor::new("Wi-Fi".to_string()))),
-        "ethernet_dns" => Some(Box::new(DNSServerEditor::new("Ethernet".to_string()))),
+        "default_microphone" => Some(Box::new(DefaultInputDeviceEditor::new())),
+        "master_volume" => Some(Box::new(VolumeEditor::new())),
         "wifi_power_mode" => Some(Box::new(WiFiPowerEditor::new())),
-        _ => None,
+        "firewall" => Some(Box::new(FirewallEditor::new())),
+        "bluetooth" => Some(Box::new(BluetoothEditor::new())),
+        "brightness" => Some(Box::new(BrightnessEditor::new())),
+        "display_scaling" => Some(Box::new(ScalingEditor::new())),
+        "app_theme" => Some(Box::new(AppThemeEditor::new())),
+        "hostname" => Some(Box::new(HostnameEditor::new())),
+        "timezone" => Some(Box::new(TimezoneEditor::new())),
+        "pause_updates" => Some(Box::new(UpdatePauseEditor::new())),
+        "startup_apps" => Some(Box::new(StartupAppsEditor::new())),
+        "mouse_speed" => Some(Box::new(MouseSpeedEditor::new())),
+        "keyboard_delay" => Some(Box::new(KeyboardRepeatEditor::new(KeyboardRepeat::Delay))),
+        "keyboard_speed" => Some(Box::new(KeyboardRepeatEditor::new(KeyboardRepeat::Speed))),
+        key => {
+            // Per-adapter editors carry the discovered adapter name in their key
+            if let Some(adapter) = key.strip_prefix("adapter_toggle:") {
+                Some(Box::new(NetworkAdapterToggleEditor::new(adapter.to_string())))
+            } else if let Some(adapter) = key.strip_prefix("adapter_dns:") {
+                Some(Box::new(DNSServerEditor::new(adapter.to_string())))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const MODES: [(u32, u32, u32); 2] = [(1920, 1080, 60), (1280, 720, 60)];
+
+    fn resolution((width, height, refresh): (u32, u32, u32)) -> SettingValue {
+        SettingValue::Resolution { width, height, refresh }
+    }
+
+    #[test]
+    fn accepts_a_listed_display_mode() {
+        let editor = DisplaySettingsEditor::with_modes(Some(r"\\.\DISPLAY1"), MODES.to_vec());
+        assert!(editor.validate_value(&resolution((1920, 1080, 60))).unwrap());
+    }
+
+    #[test]
+    fn rejects_an_unlisted_display_mode() {
+        let editor = DisplaySettingsEditor::with_modes(Some(r"\\.\DISPLAY1"), MODES.to_vec());
+        assert!(!editor.validate_value(&resolution((1920, 1080, 144))).unwrap());
+        assert!(!editor.validate_value(&SettingValue::Selection("1920x1080".to_string())).unwrap());
+    }
+
+    #[test]
+    fn rejects_modes_without_a_target_display() {
+        let editor = DisplaySettingsEditor::with_modes(None, MODES.to_vec());
+        assert!(!editor.validate_value(&resolution((1920, 1080, 60))).unwrap());
+    }
+
+    static ENUMERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_modes(_device_name: &str) -> Vec<(u32, u32, u32)> {
+        ENUMERATIONS.fetch_add(1, Ordering::SeqCst);
+        MODES.to_vec()
+    }
+
+    #[test]
+    fn enumerates_modes_once_per_edit_session() {
+        let mut editor = DisplaySettingsEditor::with_enumerator(r"\\.\DISPLAY1", counted_modes);
+        
+        // The worker loads the options and validates the choice on separate copies
+        let options = editor.clone_box().get_available_options().unwrap();
+        assert_eq!(options.len(), MODES.len());
+        assert!(editor.clone_box().validate_value(&options[0].value).unwrap());
+        assert!(editor.validate_value(&options[1].value).unwrap());
+        assert_eq!(ENUMERATIONS.load(Ordering::SeqCst), 1);
+        
+        editor.refresh_cache();
+        assert!(editor.validate_value(&options[0].value).unwrap());
+        assert_eq!(ENUMERATIONS.load(Ordering::SeqCst), 2);
+    }
+
+    /// GUID and name of the scheme on `line`
+    fn scheme(line: &str) -> Option<(String, String)> {
+        parse_power_scheme(line).map(|scheme| (scheme.guid, scheme.name))
+    }
+
+    fn named(guid: &str, name: &str) -> Option<(String, String)> {
+        Some((guid.to_string(), name.to_string()))
+    }
+
+    #[test]
+    fn parses_the_english_scheme_list() {
+        let output = "\
+Existing Power Schemes (* Active)
+-----------------------------------
+Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *
+Power Scheme GUID: 8c5e7fda-e8bf-4a96-9a85-feb9fe6cc1aa  (High performance)
+Power Scheme GUID: a1841308-3541-4fab-bc81-f71556f20b4a  (Power saver)
+";
+        let schemes: Vec<_> = output.lines().map(scheme).filter(Option::is_some).collect();
+        assert_eq!(
+            schemes,
+            [
+                named("381b4222-f694-41f0-9685-ff5bb260df2e", "Balanced"),
+                named("8c5e7fda-e8bf-4a96-9a85-feb9fe6cc1aa", "High performance"),
+                named("a1841308-3541-4fab-bc81-f71556f20b4a", "Power saver"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_a_localized_scheme_line() {
+        assert_eq!(
+            scheme("GUID du mode de gestion de l'alimentation: 381b4222-f694-41f0-9685-ff5bb260df2e  (Utilisation normale) *"),
+            named("381b4222-f694-41f0-9685-ff5bb260df2e", "Utilisation normale")
+        );
+    }
+
+    #[test]
+    fn keeps_parentheses_inside_scheme_names() {
+        assert_eq!(
+            scheme("Power Scheme GUID: 0d3c7a3e-58e4-4c4a-8a2e-2f3f4b5c6d7e  (Gaming (quiet fans))"),
+            named("0d3c7a3e-58e4-4c4a-8a2e-2f3f4b5c6d7e", "Gaming (quiet fans)")
+        );
+    }
+
+    #[test]
+    fn drops_the_active_marker() {
+        assert_eq!(
+            scheme("Power Scheme GUID: 8c5e7fda-e8bf-4a96-9a85-feb9fe6cc1aa  (High performance) *"),
+            named("8c5e7fda-e8bf-4a96-9a85-feb9fe6cc1aa", "High performance")
+        );
+    }
+
+    #[test]
+    fn names_unnamed_schemes_after_their_guid() {
+        assert_eq!(
+            scheme("Power Scheme GUID: 0d3c7a3e-58e4-4c4a-8a2e-2f3f4b5c6d7e *"),
+            named("0d3c7a3e-58e4-4c4a-8a2e-2f3f4b5c6d7e", "0d3c7a3e-58e4-4c4a-8a2e-2f3f4b5c6d7e")
+        );
+    }
+
+    #[test]
+    fn finds_no_guid_in_other_lines() {
+        assert_eq!(find_guid("Existing Power Schemes (* Active)"), None);
+        assert_eq!(find_guid("381b4222-f694-41f0-9685"), None);
+        assert_eq!(
+            find_guid("é 381b4222-f694-41f0-9685-ff5bb260df2e"),
+            Some((3, "381b4222-f694-41f0-9685-ff5bb260df2e"))
+        );
     }
 }
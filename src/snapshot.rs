@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use crate::{
+    app::StagedChange,
+    config::app_dir,
+    settings::SettingsItem,
+    settings_editor::{create_editor, SettingEditor, SettingValue},
+};
+
+/// One editable setting's value as recorded in a snapshot file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub name: String,
+    pub editor_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<SettingValue>,
+    /// Why the value couldn't be read, in place of `value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Folder snapshots are exported to, i.e. `%APPDATA%\TMWT\snapshots`
+pub fn snapshots_dir() -> Option<PathBuf> {
+    app_dir().map(|dir| dir.join("snapshots"))
+}
+
+/// Editor that holds the actual value for `editor_key`. Editors that start by
+/// picking a target (like the monitor for display resolution) are followed to the
+/// target that is currently active.
+pub fn resolve_editor(editor_key: &str) -> Result<Box<dyn SettingEditor>> {
+    let editor = create_editor(editor_key)
+        .with_context(|| format!("No editor is available for '{}'", editor_key))?;
+    let current = editor.get_current_value()?;
+    Ok(editor.select_target(&current).unwrap_or(editor))
+}
+
+/// Read the current value of every item with an inline editor. Items that fail are
+/// recorded with their error so one broken editor doesn't lose the rest.
+pub fn capture(items: &[SettingsItem]) -> Vec<SnapshotEntry> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let editor_key = item.editor_key.clone()?;
+            let result = resolve_editor(&editor_key).and_then(|editor| editor.get_current_value());
+            let (value, error) = match result {
+                Ok(value) => (Some(value), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            Some(SnapshotEntry {
+                name: item.name.clone(),
+                editor_key,
+                value,
+                error,
+            })
+        })
+        .collect()
+}
+
+/// Capture every editable setting and write it to a new file in `snapshots_dir`, returning its path
+pub fn export(items: &[SettingsItem]) -> Result<PathBuf> {
+    let entries = capture(items);
+
+    let dir = snapshots_dir().context("Could not locate the application data folder")?;
+    fs::create_dir_all(&dir).context("Failed to create snapshots folder")?;
+
+    // Named after the machine so snapshots from several PCs can sit side by side
+    let machine = std::env::var("COMPUTERNAME").unwrap_or_else(|_| "snapshot".to_string());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = dir.join(format!("{}-{}.json", machine, timestamp));
+
+    let contents = serde_json::to_string_pretty(&entries)?;
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Snapshot files in `snapshots_dir`, newest first
+pub fn list_snapshots() -> Vec<PathBuf> {
+    let Some(entries) = snapshots_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let modified = fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .unwrap_or(UNIX_EPOCH);
+            (modified, path)
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<SnapshotEntry>> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("{} is not a valid snapshot", path.display()))
+}
+
+/// Why an entry can't be applied, known before anything is changed
+pub fn skip_reason(entry: &SnapshotEntry) -> Option<String> {
+    if create_editor(&entry.editor_key).is_none() {
+        return Some(format!("'{}' is not recognized on this machine", entry.editor_key));
+    }
+    if entry.value.is_none() {
+        return Some(format!(
+            "no value was captured ({})",
+            entry.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+    None
+}
+
+#[derive(Debug, Clone)]
+pub enum ApplyOutcome {
+    Changed,
+    /// The setting already had the requested value
+    Unchanged,
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ApplyResult {
+    pub name: String,
+    pub outcome: ApplyOutcome,
+}
+
+/// Apply each entry through its editor's validated `set_value`, carrying on past failures
+pub fn apply(entries: &[SnapshotEntry]) -> Vec<ApplyResult> {
+    entries
+        .iter()
+        .map(|entry| {
+            let outcome = match (&entry.value, skip_reason(entry)) {
+                (Some(value), None) => match resolve_editor(&entry.editor_key)
+                    .and_then(|editor| set_if_changed(editor.as_ref(), value.clone()))
+                {
+                    Ok(true) => ApplyOutcome::Changed,
+                    Ok(false) => ApplyOutcome::Unchanged,
+                    Err(e) => ApplyOutcome::Failed(e.to_string()),
+                },
+                (_, reason) => ApplyOutcome::Skipped(reason.unwrap_or_default()),
+            };
+            ApplyResult {
+                name: entry.name.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Apply changes staged in the edit panel through the editors they were made with,
+/// which can be a target (like a second monitor) that `resolve_editor` wouldn't pick
+pub fn apply_staged(changes: &[StagedChange]) -> Vec<ApplyResult> {
+    changes
+        .iter()
+        .map(|change| {
+            let outcome = match set_if_changed(change.editor.as_ref(), change.value.clone()) {
+                Ok(true) => ApplyOutcome::Changed,
+                Ok(false) => ApplyOutcome::Unchanged,
+                Err(e) => ApplyOutcome::Failed(e.to_string()),
+            };
+            ApplyResult {
+                name: change.item_name.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Set `value` unless the setting already has it, returning whether anything changed
+fn set_if_changed(editor: &dyn SettingEditor, value: SettingValue) -> Result<bool> {
+    if !editor.can_write() {
+        anyhow::bail!("this setting can't be changed from TMWT yet");
+    }
+    if editor.get_current_value()? == value {
+        return Ok(false);
+    }
+    if !editor.validate_value(&value)? {
+        anyhow::bail!("{} is not a valid value", value);
+    }
+    editor.set_value(value)?;
+    Ok(true)
+}
@@ -0,0 +1,333 @@
+use crate::registry::{delete_value, read_string, write_string};
+use crate::settings_editor::{from_wide, to_wide, CommandExt, EditorType, SettingEditor, SettingOption, SettingValue};
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use windows::{
+    core::{PCWSTR, PWSTR},
+    Win32::System::Registry::HKEY_LOCAL_MACHINE,
+    Win32::System::SystemInformation::{
+        ComputerNamePhysicalDnsHostname, ComputerNamePhysicalNetBIOS, GetComputerNameExW, SetComputerNameExW,
+    },
+};
+
+/// Characters Windows rejects in a computer name
+const ILLEGAL_HOSTNAME_CHARS: &str = r#"\/:*?"<>|,~!@#$%^&'.(){}"#;
+
+/// Computer name editor. The new name only takes effect after a restart.
+#[derive(Debug, Clone, Default)]
+pub struct HostnameEditor;
+
+impl HostnameEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SettingEditor for HostnameEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let mut buffer = [0u16; 256];
+        let mut size = buffer.len() as u32;
+        unsafe { GetComputerNameExW(ComputerNamePhysicalNetBIOS, PWSTR(buffer.as_mut_ptr()), &mut size) }
+            .context("Failed to read the computer name")?;
+        Ok(SettingValue::String(from_wide(&buffer)))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::String(name) = value {
+            // Setting the DNS host name updates the NetBIOS name to match
+            let name_wide = to_wide(name.trim());
+            unsafe { SetComputerNameExW(ComputerNamePhysicalDnsHostname, PCWSTR(name_wide.as_ptr())) }
+                .context("Failed to rename the computer")?;
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for computer name")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        let SettingValue::String(name) = value else {
+            return Ok(false);
+        };
+        let name = name.trim();
+        
+        // NetBIOS limits
+        match name.chars().count() {
+            0 => anyhow::bail!("Enter a computer name"),
+            1..=15 => {}
+            _ => anyhow::bail!("Computer names can be at most 15 characters"),
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| c.is_whitespace() || ILLEGAL_HOSTNAME_CHARS.contains(*c))
+        {
+            anyhow::bail!("Computer names can't contain '{}'", c);
+        }
+        if name.chars().all(|c| c.is_ascii_digit()) {
+            anyhow::bail!("Computer names can't be only numbers");
+        }
+        Ok(true)
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::TextInput { multiline: false }
+    }
+    
+    fn requires_admin(&self) -> bool {
+        true
+    }
+}
+
+/// Time zone editor backed by `tzutil`
+#[derive(Debug, Clone, Default)]
+pub struct TimezoneEditor;
+
+impl TimezoneEditor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Every time zone as (id, display name), sorted by display name
+    fn get_time_zones(&self) -> Result<Vec<(String, String)>> {
+        let output = Command::new("tzutil")
+            .args(["/l"])
+            .checked_output("Failed to list time zones")?;
+        
+        let mut zones = parse_time_zones(&String::from_utf8_lossy(&output.stdout));
+        if zones.is_empty() {
+            anyhow::bail!("tzutil returned no time zones");
+        }
+        zones.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(zones)
+    }
+}
+
+/// Parse `tzutil /l`, which prints each zone as its display name, its id and a blank line
+fn parse_time_zones(output: &str) -> Vec<(String, String)> {
+    let mut zones = Vec::new();
+    let mut lines = output.lines().map(str::trim);
+    while let Some(display) = lines.next() {
+        if display.is_empty() {
+            continue;
+        }
+        match lines.next() {
+            Some(id) if !id.is_empty() => zones.push((id.to_string(), display.to_string())),
+            _ => break,
+        }
+    }
+    zones
+}
+
+impl SettingEditor for TimezoneEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        let output = Command::new("tzutil")
+            .args(["/g"])
+            .checked_output("Failed to get the current time zone")?;
+        let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if id.is_empty() {
+            anyhow::bail!("Could not determine the current time zone");
+        }
+        Ok(SettingValue::Selection(id))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        let zones = self.get_time_zones()?;
+        Ok(zones
+            .into_iter()
+            .map(|(id, display)| SettingOption {
+                label: display,
+                description: Some(id.clone()),
+                value: SettingValue::Selection(id),
+            })
+            .collect())
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Selection(id) = value {
+            Command::new("tzutil")
+                .args(["/s", &id])
+                .checked_output("Failed to set the time zone")?;
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for time zone")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        if let SettingValue::Selection(id) = value {
+            let zones = self.get_time_zones()?;
+            Ok(zones.iter().any(|(zone_id, _)| zone_id == id))
+        } else {
+            Ok(false)
+        }
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Dropdown
+    }
+    
+    fn requires_admin(&self) -> bool {
+        true
+    }
+}
+
+const UPDATE_SETTINGS_KEY: &str = r"SOFTWARE\Microsoft\WindowsUpdate\UX\Settings";
+
+/// How long enabling the pause holds updates back
+const UPDATE_PAUSE_DAYS: u64 = 7;
+
+/// Start and end values Settings writes when updates are paused, in that order
+const UPDATE_PAUSE_VALUES: [(&str, &str); 3] = [
+    ("PauseUpdatesStartTime", "PauseUpdatesExpiryTime"),
+    ("PauseFeatureUpdatesStartTime", "PauseFeatureUpdatesEndTime"),
+    ("PauseQualityUpdatesStartTime", "PauseQualityUpdatesEndTime"),
+];
+
+/// Format seconds since the Unix epoch the way Windows Update stores pause times,
+/// as a UTC ISO-8601 timestamp like `2024-03-01T12:00:00Z`
+fn iso8601_utc(secs: u64) -> String {
+    let (days, time) = (secs / 86_400, secs % 86_400);
+    
+    // Days since the epoch to a civil date, after Howard Hinnant's `civil_from_days`
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Pauses Windows Update for a week, or resumes it
+#[derive(Debug, Clone, Default)]
+pub struct UpdatePauseEditor;
+
+impl UpdatePauseEditor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SettingEditor for UpdatePauseEditor {
+    fn clone_box(&self) -> Box<dyn SettingEditor> {
+        Box::new(self.clone())
+    }
+
+    fn get_current_value(&self) -> Result<SettingValue> {
+        // Timestamps in the same format compare correctly as strings, and an
+        // expired pause is left in the registry until the next change
+        let now = iso8601_utc(now_secs());
+        let paused = read_string(HKEY_LOCAL_MACHINE, UPDATE_SETTINGS_KEY, "PauseUpdatesExpiryTime")?
+            .is_some_and(|expiry| expiry > now);
+        Ok(SettingValue::Bool(paused))
+    }
+    
+    fn get_available_options(&self) -> Result<Vec<SettingOption>> {
+        Ok(vec![
+            SettingOption {
+                label: "Paused".to_string(),
+                value: SettingValue::Bool(true),
+                description: Some(format!("Hold back updates for {} days", UPDATE_PAUSE_DAYS)),
+            },
+            SettingOption {
+                label: "Active".to_string(),
+                value: SettingValue::Bool(false),
+                description: Some("Resume installing updates".to_string()),
+            },
+        ])
+    }
+    
+    fn set_value(&self, value: SettingValue) -> Result<()> {
+        if let SettingValue::Bool(pause) = value {
+            if pause {
+                let now = now_secs();
+                let start = iso8601_utc(now);
+                let end = iso8601_utc(now + UPDATE_PAUSE_DAYS * 86_400);
+                for (start_name, end_name) in UPDATE_PAUSE_VALUES {
+                    write_string(HKEY_LOCAL_MACHINE, UPDATE_SETTINGS_KEY, start_name, &start)?;
+                    write_string(HKEY_LOCAL_MACHINE, UPDATE_SETTINGS_KEY, end_name, &end)?;
+                }
+            } else {
+                for (start_name, end_name) in UPDATE_PAUSE_VALUES {
+                    delete_value(HKEY_LOCAL_MACHINE, UPDATE_SETTINGS_KEY, start_name)?;
+                    delete_value(HKEY_LOCAL_MACHINE, UPDATE_SETTINGS_KEY, end_name)?;
+                }
+            }
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid value type for update pause")
+        }
+    }
+    
+    fn validate_value(&self, value: &SettingValue) -> Result<bool> {
+        Ok(matches!(value, SettingValue::Bool(_)))
+    }
+    
+    fn get_editor_type(&self) -> EditorType {
+        EditorType::Toggle
+    }
+    
+    fn requires_admin(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_the_epoch() {
+        assert_eq!(iso8601_utc(0), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn formats_a_leap_day() {
+        assert_eq!(iso8601_utc(1_709_164_800), "2024-02-29T00:00:00Z");
+        assert_eq!(iso8601_utc(1_709_164_800 + 86_400), "2024-03-01T00:00:00Z");
+    }
+
+    #[test]
+    fn formats_across_a_century_boundary() {
+        assert_eq!(iso8601_utc(946_684_799), "1999-12-31T23:59:59Z");
+        assert_eq!(iso8601_utc(946_684_800), "2000-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn pause_end_lands_a_week_later() {
+        let start = 1_708_849_800;
+        assert_eq!(iso8601_utc(start), "2024-02-25T08:30:00Z");
+        assert_eq!(iso8601_utc(start + UPDATE_PAUSE_DAYS * 86_400), "2024-03-03T08:30:00Z");
+    }
+}
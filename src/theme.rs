@@ -0,0 +1,77 @@
+use ratatui::style::Color;
+
+/// Semantic colors used by every draw function instead of hardcoded `Color`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    /// Regular text and unfocused borders
+    pub text: Color,
+    /// Focused borders, the selected row and other things that need attention
+    pub accent: Color,
+    /// Headings, labels and secondary emphasis
+    pub highlight: Color,
+    /// Descriptions, hints and other de-emphasized text
+    pub muted: Color,
+    pub error: Color,
+    pub success: Color,
+    /// Background of the selected list row
+    pub selection: Color,
+    /// Text drawn on top of an `accent` background, like the text cursor
+    pub on_accent: Color,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        name: "dark",
+        text: Color::White,
+        accent: Color::Yellow,
+        highlight: Color::Cyan,
+        muted: Color::DarkGray,
+        error: Color::Red,
+        success: Color::Green,
+        selection: Color::DarkGray,
+        on_accent: Color::Black,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        name: "light",
+        text: Color::Black,
+        accent: Color::Blue,
+        highlight: Color::Magenta,
+        muted: Color::DarkGray,
+        error: Color::Red,
+        success: Color::Green,
+        selection: Color::Gray,
+        on_accent: Color::White,
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        name: "high_contrast",
+        text: Color::White,
+        accent: Color::LightYellow,
+        highlight: Color::LightCyan,
+        muted: Color::Gray,
+        error: Color::LightRed,
+        success: Color::LightGreen,
+        selection: Color::Blue,
+        on_accent: Color::Black,
+    };
+
+    pub const ALL: [Theme; 3] = [Theme::DARK, Theme::LIGHT, Theme::HIGH_CONTRAST];
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        Self::ALL.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// The built-in theme after this one, wrapping around
+    pub fn next(&self) -> Theme {
+        let index = Self::ALL.iter().position(|theme| theme == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DARK
+    }
+}
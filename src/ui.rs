@@ -1,40 +1,192 @@
-use crate::app::{App, FocusArea, InputMode};
+use crate::app::{App, BatchState, DetailStatus, FocusArea, InputMode};
+use crate::exec;
+use crate::settings::LaunchType;
+use crate::settings_editor;
+use crate::snapshot::{self, ApplyOutcome};
+use crate::theme::Theme;
+use crate::keybindings::{Action, KeyBindings, FIXED_EDIT_KEYS, FIXED_NORMAL_KEYS, FIXED_SEARCH_KEYS, HELP_SECTIONS, SEARCH_ACTIONS};
+use serde::{Deserialize, Serialize};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Wrap,
+    },
     Frame,
 };
 
 pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+    let chunks = screen_layout(f.size());
+    
+    draw_header(f, app, chunks[0]);
+    draw_main_content(f, app, chunks[1]);
+    draw_status_bar(f, app, chunks[2]);
+}
+
+fn screen_layout(size: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(5),
             Constraint::Length(3),
         ])
-        .split(f.size());
+        .split(size)
+}
+
+/// Screen areas of the categories and items panes, for hit-testing mouse events
+pub fn pane_areas(size: Rect, app: &App) -> (Rect, Rect) {
+    let (panes_area, _) = split_details(screen_layout(size)[1], app.show_details);
+    let chunks = main_content_layout(panes_area, app.categories_width);
+    (chunks[0], chunks[1])
+}
+
+/// Take the detail pane off the bottom of the main content when it is open
+fn split_details(area: Rect, show_details: bool) -> (Rect, Option<Rect>) {
+    if !show_details {
+        return (area, None);
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(DETAIL_HEIGHT)])
+        .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+/// Categories on the left taking `categories_width` percent, items on the right
+fn main_content_layout(area: Rect, categories_width: u16) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(categories_width),
+            Constraint::Percentage(100 - categories_width),
+        ])
+        .split(area)
+}
+
+/// Rows of the detail pane, including its border
+const DETAIL_HEIGHT: u16 = 8;
+
+/// How much of each entry the items list shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListDensity {
+    /// Name line only, fitting twice as many entries
+    Compact,
+    /// Name line plus description line
+    #[default]
+    Detailed,
+}
+
+impl ListDensity {
+    pub fn toggled(self) -> Self {
+        match self {
+            ListDensity::Compact => ListDensity::Detailed,
+            ListDensity::Detailed => ListDensity::Compact,
+        }
+    }
     
-    draw_header(f, app, chunks[0]);
-    draw_main_content(f, app, chunks[1]);
-    draw_status_bar(f, app, chunks[2]);
+    /// Rows each entry of the items list occupies
+    pub fn item_height(self) -> usize {
+        match self {
+            ListDensity::Compact => 1,
+            ListDensity::Detailed => 2,
+        }
+    }
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            ListDensity::Compact => "compact",
+            ListDensity::Detailed => "detailed",
+        }
+    }
+}
+
+/// A row of the items list: a subcategory heading, or the item at an index of `filtered_items`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemRow<'a> {
+    Heading(&'a str),
+    Item(usize),
+}
+
+/// Rows of the items list, with a heading above the first item of each subcategory.
+/// Search results are ordered by relevance rather than grouped, so they get none.
+pub fn item_rows(app: &App) -> Vec<ItemRow<'_>> {
+    let mut rows = Vec::with_capacity(app.filtered_items.len());
+    let mut group = None;
+    for (index, item) in app.filtered_items.iter().enumerate() {
+        if let Some(subcategory) = item.subcategory.as_deref().filter(|_| app.search_query.is_empty()) {
+            if group != Some(subcategory) {
+                group = Some(subcategory);
+                rows.push(ItemRow::Heading(subcategory));
+            }
+        }
+        rows.push(ItemRow::Item(index));
+    }
+    rows
+}
+
+fn row_height(app: &App, row: ItemRow) -> usize {
+    match row {
+        ItemRow::Heading(_) => 1,
+        ItemRow::Item(_) => app.density.item_height(),
+    }
+}
+
+/// Row of the selected item in `rows`
+fn selected_row(app: &App, rows: &[ItemRow]) -> Option<usize> {
+    rows.iter().position(|row| *row == ItemRow::Item(app.item_index))
+}
+
+/// First visible row of the items list, mirroring how `List` scrolls a fresh
+/// `ListState` so the selected item ends up at the bottom of the list
+fn items_list_offset(app: &App, rows: &[ItemRow], area: Rect) -> usize {
+    let height = area.height.saturating_sub(2) as usize;
+    let Some(selected) = selected_row(app, rows) else {
+        return 0;
+    };
+    let mut first = selected;
+    let mut used = row_height(app, rows[selected]);
+    while first > 0 && used + row_height(app, rows[first - 1]) <= height {
+        first -= 1;
+        used += row_height(app, rows[first]);
+    }
+    first
+}
+
+/// Index in `filtered_items` of the item drawn `row` lines below the top border of the
+/// items list in `area`, or `None` for headings and the empty space below the last item
+pub fn item_at_row(app: &App, area: Rect, row: usize) -> Option<usize> {
+    let rows = item_rows(app);
+    let mut bottom = 0;
+    for item_row in &rows[items_list_offset(app, &rows, area)..] {
+        bottom += row_height(app, *item_row);
+        if row < bottom {
+            return match item_row {
+                ItemRow::Item(index) => Some(*index),
+                ItemRow::Heading(_) => None,
+            };
+        }
+    }
+    None
 }
 
 fn draw_header(f: &mut Frame, app: &App, area: Rect) {
-    let header_text = if app.input_mode == InputMode::Search {
+    let mut header_text = if app.input_mode == InputMode::Search {
         vec![
             Span::raw("Windows System Settings TUI - "),
-            Span::styled("Search: ", Style::default().fg(Color::Yellow)),
-            Span::styled(&app.search_query, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Search: ", Style::default().fg(app.theme.accent)),
+            Span::styled(&app.search_query, Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)),
         ]
-    } else if app.input_mode == InputMode::Edit {
+    } else if app.input_mode == InputMode::Edit || app.input_mode == InputMode::Confirm {
         vec![
             Span::styled(
                 "Windows System Settings TUI - Edit Mode",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(app.theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]
@@ -42,34 +194,146 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         vec![Span::styled(
             "Windows System Settings TUI",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]
     };
     
-    let header = Paragraph::new(Line::from(header_text))
-        .style(Style::default().fg(Color::White))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)),
+    header_text.push(if app.elevated {
+        Span::styled("  [Administrator]", Style::default().fg(app.theme.success))
+    } else {
+        Span::styled(
+            format!(
+                "  [Not elevated - {} to relaunch as admin]",
+                app.keybindings.describe_keys(Action::RelaunchElevated)
+            ),
+            Style::default().fg(app.theme.muted),
         )
+    });
+    
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().fg(app.theme.text));
+    if let Some(breadcrumb) = breadcrumb(app) {
+        block = block.title(
+            Title::from(Span::styled(breadcrumb, Style::default().fg(app.theme.muted)))
+                .position(Position::Bottom)
+                .alignment(Alignment::Left),
+        );
+    }
+    
+    let header = Paragraph::new(Line::from(header_text))
+        .style(Style::default().fg(app.theme.text))
+        .block(block)
         .alignment(Alignment::Center);
     
     f.render_widget(header, area);
 }
 
+/// Where the selection is, e.g. " Network & Internet › Wi-Fi DNS Settings ". Search
+/// results come from every category, so there the selected item's own category is shown.
+fn breadcrumb(app: &App) -> Option<String> {
+    let item = app.filtered_items.get(app.item_index);
+    let path = if app.search_query.is_empty() {
+        let category = app.categories.get(app.category_index)?.display_name();
+        match item {
+            Some(item) => format!("{} › {}", category, app.display_name(item)),
+            None => category.to_string(),
+        }
+    } else {
+        match item {
+            Some(item) => format!("Search › {} › {}", item.category.display_name(), app.display_name(item)),
+            None => "Search".to_string(),
+        }
+    };
+    Some(format!(" {} ", path))
+}
+
 fn draw_main_content(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-        .split(area);
+    let (panes_area, details_area) = split_details(area, app.show_details);
+    let chunks = main_content_layout(panes_area, app.categories_width);
     
     draw_categories(f, app, chunks[0]);
     draw_items(f, app, chunks[1]);
+    if let Some(details_area) = details_area {
+        draw_details(f, app, details_area);
+    }
+}
+
+fn draw_details(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Details")
+        .border_style(Style::default().fg(app.theme.text));
+    
+    let Some(item) = app.filtered_items.get(app.item_index) else {
+        let empty = Paragraph::new("No item selected")
+            .style(Style::default().fg(app.theme.muted))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    };
+    
+    let label_style = Style::default().fg(app.theme.highlight);
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![Span::styled(format!("{:<10}", label), label_style), Span::raw(value)])
+    };
+    
+    let mut lines = vec![Line::from(Span::styled(
+        item.description.clone().unwrap_or_else(|| item.name.clone()),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    
+    let value_line = match app.detail_value.as_ref().map(|detail| &detail.status) {
+        Some(DetailStatus::Loaded(value)) => field("Current", value.clone()),
+        Some(DetailStatus::Failed(e)) => Line::from(vec![
+            Span::styled(format!("{:<10}", "Current"), label_style),
+            Span::styled(format!("unavailable ({})", e), Style::default().fg(app.theme.error)),
+        ]),
+        Some(DetailStatus::Loading) => field("Current", "Loading...".to_string()),
+        Some(DetailStatus::NotEditable) | None => {
+            field("Current", "not readable here - opens in Windows".to_string())
+        }
+    };
+    lines.push(value_line);
+    if app.aliases.contains_key(&item.name) {
+        lines.push(field("Renamed", format!("from {}", item.name)));
+    }
+    lines.push(field("Opens", launch_description(&item.launch_command)));
+    lines.push(field("Admin", if item.requires_admin { "required" } else { "not required" }.to_string()));
+    if !item.keywords.is_empty() {
+        lines.push(field("Keywords", item.keywords.join(", ")));
+    }
+    
+    let details = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: true });
+    f.render_widget(details, area);
+}
+
+fn launch_description(launch: &LaunchType) -> String {
+    match launch {
+        LaunchType::MsSettings(page) => format!("Settings app (ms-settings:{})", page),
+        LaunchType::ControlPanel(applet) => format!("Control Panel ({})", applet),
+        LaunchType::RunDll32(command) => format!("rundll32 {}", command),
+        LaunchType::PowerShell(command) => format!("PowerShell: {}", command),
+        LaunchType::Command(command) => format!("Command: {}", command),
+        LaunchType::WindowsTerminal { profile, command } => {
+            let mut description = "Windows Terminal".to_string();
+            if let Some(profile) = profile {
+                description.push_str(&format!(" (profile {})", profile));
+            }
+            if let Some(command) = command {
+                description.push_str(&format!(": {}", command));
+            }
+            description
+        }
+        LaunchType::Url(url) => format!("Browser ({})", url),
+    }
 }
 
 fn draw_categories(f: &mut Frame, app: &App, area: Rect) {
+    let counts = app.category_counts();
     let categories: Vec<ListItem> = app
         .categories
         .iter()
@@ -80,21 +344,26 @@ fn draw_categories(f: &mut Frame, app: &App, area: Rect) {
             } else {
                 "   ".to_string()
             };
+            let label = format!("{} ({})", category.display_name(), counts[i]);
             
             let content = if i == app.category_index {
                 Line::from(vec![
                     Span::raw(prefix),
                     Span::styled(
-                        category.display_name(),
+                        label,
                         Style::default()
-                            .fg(Color::Yellow)
+                            .fg(app.theme.accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                 ])
+            } else if !app.search_query.is_empty() && counts[i] == 0 {
+                // Nothing in here matches the search
+                Line::from(vec![Span::raw(prefix), Span::raw(label)])
+                    .style(Style::default().fg(app.theme.muted))
             } else {
                 Line::from(vec![
                     Span::raw(prefix),
-                    Span::raw(category.display_name()),
+                    Span::raw(label),
                 ])
             };
             
@@ -106,10 +375,10 @@ fn draw_categories(f: &mut Frame, app: &App, area: Rect) {
         .borders(Borders::ALL)
         .title("Categories")
         .border_style(
-            if app.focus_area == FocusArea::Categories && app.input_mode != InputMode::Edit {
-                Style::default().fg(Color::Yellow)
+            if app.focus_area == FocusArea::Categories && app.input_mode == InputMode::Normal {
+                Style::default().fg(app.theme.accent)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(app.theme.text)
             },
         );
     
@@ -117,49 +386,118 @@ fn draw_categories(f: &mut Frame, app: &App, area: Rect) {
         .block(categories_block)
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(app.theme.selection)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
     
     f.render_widget(categories_list, area);
+    
+    let visible = area.height.saturating_sub(2) as usize;
+    if app.categories.len() > visible {
+        let mut state = ScrollbarState::new(app.categories.len())
+            .viewport_content_length(visible)
+            .position(app.category_index);
+        draw_scrollbar(f, area.inner(&Margin { vertical: 1, horizontal: 0 }), &mut state, &app.theme);
+    }
 }
 
 fn draw_items(f: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .filtered_items
-        .iter()
-        .enumerate()
-        .map(|(i, item)| {
-            let icon = item.icon.unwrap_or('•');
-            let admin_indicator = if item.requires_admin { " [Admin]" } else { "" };
-            let edit_indicator = if item.can_edit_inline { " ✏" } else { "" };
-            
-            let style = if i == app.item_index && app.focus_area == FocusArea::Items {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-            
-            let content = vec![
-                Line::from(vec![
-                    Span::raw(format!("{} ", icon)),
-                    Span::styled(&item.name, style),
-                    Span::styled(admin_indicator, Style::default().fg(Color::Red)),
-                    Span::styled(edit_indicator, Style::default().fg(Color::Green)),
-                ]),
+    // Inside the borders and after the "→ " highlight symbol
+    let value_width = area.width.saturating_sub(4) as usize;
+    let item_entry = |i: usize| {
+        let item = &app.filtered_items[i];
+        let icon = item.icon.unwrap_or('•');
+        let favorite = app.favorites.contains(&item.name);
+        let favorite_indicator = match (favorite, app.screen_reader) {
+            (false, _) => "",
+            (true, false) => "★ ",
+            (true, true) => "[favorite] ",
+        };
+        let admin_indicator = if item.requires_admin { " [Admin]" } else { "" };
+        let editor = item.editor_key.as_deref().and_then(settings_editor::create_editor);
+        // Editable items whose editor can't read or save yet are flagged so nobody edits them for nothing
+        let (edit_indicator, edit_color) = match &editor {
+            _ if !item.can_edit_inline => ("", app.theme.success),
+            Some(editor) if editor.is_fully_implemented() => {
+                (if app.screen_reader { " [editable]" } else { " ✏" }, app.theme.success)
+            }
+            _ => (if app.screen_reader { " [not editable yet]" } else { " ⚠" }, app.theme.error),
+        };
+        let requires_restart = item.requires_restart || editor.as_ref().is_some_and(|editor| editor.requires_restart());
+        let restart_indicator = match (requires_restart, app.screen_reader) {
+            (false, _) => "",
+            (true, false) => " ⟳",
+            (true, true) => " [needs restart]",
+        };
+        
+        let style = if i == app.item_index && app.focus_area == FocusArea::Items {
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        
+        // Search results come from every category, so show where each one lives
+        let category_label = if app.search_query.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", item.category.display_name())
+        };
+        
+        let mut title = Line::from(vec![
+            Span::styled(favorite_indicator, Style::default().fg(app.theme.accent)),
+            Span::raw(format!("{} ", icon)),
+            Span::styled(app.display_name(item), style),
+            Span::styled(admin_indicator, Style::default().fg(app.theme.error)),
+            Span::styled(edit_indicator, Style::default().fg(edit_color)),
+            Span::styled(restart_indicator, Style::default().fg(app.theme.highlight)),
+            Span::styled(category_label, Style::default().fg(app.theme.highlight)),
+        ]);
+        // Right-align the current value, or the staged one, leaving it out when the row
+        // is too narrow for both
+        let staged = app.staged.iter().find(|change| change.item_name == item.name);
+        let value = match staged {
+            Some(change) => Some(Span::styled(
+                format!("  staged → {}", change.label),
+                Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
+            )),
+            None => app
+                .item_values
+                .get(&item.name)
+                .map(|value| Span::styled(format!("  {}", value), Style::default().fg(app.theme.highlight))),
+        };
+        if let Some(value) = value {
+            if let Some(padding) = value_width.checked_sub(title.width() + value.width()) {
+                title.spans.push(Span::raw(" ".repeat(padding)));
+                title.spans.push(value);
+            }
+        }
+        
+        match app.density {
+            ListDensity::Compact => ListItem::new(title),
+            ListDensity::Detailed => ListItem::new(vec![
+                title,
                 Line::from(vec![
                     Span::raw("  "),
                     Span::styled(
                         item.description.as_deref().unwrap_or(""),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(app.theme.muted),
                     ),
                 ]),
-            ];
-            
-            ListItem::new(content)
+            ]),
+        }
+    };
+    let rows = item_rows(app);
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| match *row {
+            ItemRow::Heading(subcategory) => ListItem::new(Line::from(Span::styled(
+                subcategory,
+                Style::default().fg(app.theme.highlight).add_modifier(Modifier::UNDERLINED),
+            ))),
+            ItemRow::Item(i) => item_entry(i),
         })
         .collect();
     
@@ -171,16 +509,16 @@ fn draw_items(f: &mut Frame, app: &App, area: Rect) {
             "Search Results"
         })
         .border_style(
-            if app.focus_area == FocusArea::Items && app.input_mode != InputMode::Edit {
-                Style::default().fg(Color::Yellow)
+            if app.focus_area == FocusArea::Items && app.input_mode == InputMode::Normal {
+                Style::default().fg(app.theme.accent)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(app.theme.text)
             },
         );
     
     if items.is_empty() {
         let empty_message = Paragraph::new("No items found")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(app.theme.muted))
             .block(items_block)
             .alignment(Alignment::Center);
         f.render_widget(empty_message, area);
@@ -189,7 +527,7 @@ fn draw_items(f: &mut Frame, app: &App, area: Rect) {
             .block(items_block)
             .highlight_style(
                 Style::default()
-                    .bg(Color::DarkGray)
+                    .bg(app.theme.selection)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("→ ");
@@ -197,27 +535,62 @@ fn draw_items(f: &mut Frame, app: &App, area: Rect) {
         f.render_stateful_widget(
             items_list,
             area,
-            &mut ratatui::widgets::ListState::default().with_selected(Some(app.item_index)),
+            // Nothing to highlight in an empty result list
+            &mut ratatui::widgets::ListState::default().with_selected(selected_row(app, &rows)),
         );
+        
+        let visible = area.height.saturating_sub(2) as usize / app.density.item_height();
+        if rows.len() > visible {
+            let mut state = ScrollbarState::new(rows.len())
+                .viewport_content_length(visible)
+                .position(selected_row(app, &rows).unwrap_or_default());
+            draw_scrollbar(f, area.inner(&Margin { vertical: 1, horizontal: 0 }), &mut state, &app.theme);
+        }
     }
 }
 
+/// Vertical scrollbar along the right edge of `area`, which for bordered panes is the border itself
+pub fn draw_scrollbar(f: &mut Frame, area: Rect, state: &mut ScrollbarState, theme: &Theme) {
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(Style::default().fg(theme.accent))
+        .track_style(Style::default().fg(theme.muted));
+    f.render_stateful_widget(scrollbar, area, state);
+}
+
 fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let status_text = if let Some(msg) = &app.status_message {
-        msg.clone()
+        msg.text.clone()
     } else {
-        let help_text = match app.input_mode {
+        let keys = &app.keybindings;
+        match app.input_mode {
             InputMode::Normal => {
                 if app.focus_area == FocusArea::Items {
-                    "[Enter] Open/Edit  [e] Edit  [Tab] Switch  [/] Search  [q] Quit"
+                    keys.hint(&[
+                        Action::Open,
+                        Action::Edit,
+                        Action::Favorite,
+                        Action::Undo,
+                        Action::SwitchFocus,
+                        Action::Search,
+                        Action::Help,
+                        Action::Quit,
+                    ])
                 } else {
-                    "[Enter] Select  [Tab] Switch  [/] Search  [q] Quit"
+                    keys.hint(&[Action::SwitchFocus, Action::Search, Action::Help, Action::Quit])
                 }
             },
-            InputMode::Search => "[Enter] Confirm  [Esc] Cancel  Type to search...",
-            InputMode::Edit => "Edit Mode Active - See edit panel for controls",
-        };
-        help_text.to_string()
+            InputMode::Search => format!("{}  [↑/↓] History  Type to search...", keys.hint(SEARCH_ACTIONS)),
+            InputMode::Edit => "Edit Mode Active - See edit panel for controls".to_string(),
+            InputMode::Confirm => "[y] Yes  [n] No".to_string(),
+            InputMode::Help => format!(
+                "[{}/{}] Close help",
+                keys.describe_keys(Action::Help),
+                keys.describe_keys(Action::Cancel)
+            ),
+            InputMode::Batch => "Import Mode Active - See import panel for controls".to_string(),
+        }
     };
     
     let items_count = format!("Items: {}", app.filtered_items.len());
@@ -228,32 +601,268 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
     };
     
     let editable_count = app.filtered_items.iter().filter(|i| i.can_edit_inline).count();
-    let edit_info = if editable_count > 0 {
+    let edit_info = if editable_count > 0 && app.screen_reader {
+        format!(" | Editable: {}", editable_count)
+    } else if editable_count > 0 {
         format!(" | Editable: {} (✏)", editable_count)
     } else {
         String::new()
     };
     
-    let status_line = format!("Status: Ready | {} | {}{}", filter_status, items_count, edit_info);
+    let staging_info = if app.staging || !app.staged.is_empty() {
+        format!(
+            " | Staging: {}, {} staged",
+            if app.staging { "on" } else { "off" },
+            app.staged.len()
+        )
+    } else {
+        String::new()
+    };
+    
+    // Kept on screen the whole time so a dry run is never mistaken for the real thing
+    let mode = if exec::is_dry_run() { "[DRY RUN]" } else { "Ready" };
+    let status_line = format!("Status: {} | {} | {}{}{}", mode, filter_status, items_count, edit_info, staging_info);
     
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(1), Constraint::Length(1)])
         .split(area);
     
+    let is_error = app.status_message.as_ref().is_some_and(|msg| msg.is_error);
     let help = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(if is_error { app.theme.error } else { app.theme.text }))
         .alignment(Alignment::Center);
     
     let status = Paragraph::new(status_line)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(app.theme.muted))
         .block(
             Block::default()
                 .borders(Borders::TOP)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(app.theme.muted)),
         )
         .alignment(Alignment::Left);
     
     f.render_widget(help, chunks[0]);
     f.render_widget(status, chunks[1]);
-}
\ No newline at end of file
+}
+
+/// Popup listing every key binding, grouped by the mode it applies in
+pub fn draw_help(f: &mut Frame, area: Rect, keybindings: &KeyBindings, theme: &Theme) {
+    let key_style = Style::default().fg(theme.highlight);
+    let key_line = |keys: String, description: &str| {
+        Line::from(vec![
+            Span::styled(format!("  {:<16}", keys), key_style),
+            Span::raw(description.to_string()),
+        ])
+    };
+
+    let mut lines = Vec::new();
+    for (section, actions) in HELP_SECTIONS {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            *section,
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )));
+        for action in *actions {
+            lines.push(key_line(keybindings.describe_keys(*action), action.description()));
+        }
+        let fixed_keys = match *section {
+            "Normal" => FIXED_NORMAL_KEYS,
+            "Edit" => FIXED_EDIT_KEYS,
+            "Search" => FIXED_SEARCH_KEYS,
+            _ => &[],
+        };
+        for (keys, description) in fixed_keys {
+            lines.push(key_line(keys.to_string(), description));
+        }
+    }
+
+    let help = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Key Bindings ")
+            .border_style(Style::default().fg(theme.accent)),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(help, area);
+}
+
+/// Row of a batch picker, marking the selected one
+fn picker_line(label: String, selected: bool, theme: &Theme) -> Line<'static> {
+    if selected {
+        Line::from(Span::styled(
+            format!("> {}", label),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(format!("  {}", label))
+    }
+}
+
+/// Snapshot import and profile overlay: the pickers, the review of intended changes and the final report
+pub fn draw_batch(f: &mut Frame, area: Rect, theme: &Theme, keybindings: &KeyBindings, batch: &BatchState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(area.inner(&Margin { horizontal: 1, vertical: 1 }));
+    let muted = Style::default().fg(theme.muted);
+
+    let (title, lines, scroll, help) = match batch {
+        BatchState::Pick { files, selected } => {
+            let lines = files
+                .iter()
+                .enumerate()
+                .map(|(i, path)| {
+                    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+                    picker_line(name.into_owned(), i == *selected, theme)
+                })
+                .collect::<Vec<_>>();
+            // Keep the selected file in view
+            let scroll = (*selected as u16).saturating_sub(chunks[0].height.saturating_sub(1));
+            let help = format!(
+                "[{}/{}] Navigate  [{}] Review  [{}] Cancel",
+                keybindings.describe_keys(Action::MoveUp),
+                keybindings.describe_keys(Action::MoveDown),
+                keybindings.describe_keys(Action::Select),
+                keybindings.describe_keys(Action::Cancel)
+            );
+            (" Import Snapshot ".to_string(), lines, scroll, help)
+        }
+        BatchState::Profiles { profiles, selected } => {
+            let lines = if profiles.is_empty() {
+                vec![Line::from(Span::styled(
+                    format!(
+                        "  No profiles yet - press {} to capture the current settings",
+                        keybindings.describe_keys(Action::CaptureProfile)
+                    ),
+                    muted,
+                ))]
+            } else {
+                profiles
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, entries))| {
+                        picker_line(format!("{} ({} settings)", name, entries.len()), i == *selected, theme)
+                    })
+                    .collect()
+            };
+            let scroll = (*selected as u16).saturating_sub(chunks[0].height.saturating_sub(1));
+            let help = format!(
+                "[{}/{}] Navigate  [{}] Apply  [{}] Capture current  [{}] Close",
+                keybindings.describe_keys(Action::MoveUp),
+                keybindings.describe_keys(Action::MoveDown),
+                keybindings.describe_keys(Action::Select),
+                keybindings.describe_keys(Action::CaptureProfile),
+                keybindings.describe_keys(Action::Cancel)
+            );
+            (" Profiles ".to_string(), lines, scroll, help)
+        }
+        BatchState::NameProfile { name } => {
+            let lines = vec![
+                Line::from(Span::styled("  Save the current settings as:", muted)),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw(format!("  {}", name)),
+                    Span::styled(" ", Style::default().bg(theme.accent)),
+                ]),
+            ];
+            let help = format!(
+                "[{}] Capture  [{}] Back",
+                keybindings.describe_keys(Action::Accept),
+                keybindings.describe_keys(Action::Cancel)
+            );
+            (" New Profile ".to_string(), lines, 0, help)
+        }
+        BatchState::Rename { item_name, alias } => {
+            let lines = vec![
+                Line::from(Span::styled(format!("  Show {} as:", item_name), muted)),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw(format!("  {}", alias)),
+                    Span::styled(" ", Style::default().bg(theme.accent)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  Leave empty to use the original name again", muted)),
+            ];
+            let help = format!(
+                "[{}] Rename  [{}] Cancel",
+                keybindings.describe_keys(Action::Accept),
+                keybindings.describe_keys(Action::Cancel)
+            );
+            (" Rename ".to_string(), lines, 0, help)
+        }
+        BatchState::Review { source, entries, scroll } => {
+            let lines = entries
+                .iter()
+                .map(|entry| match (&entry.value, snapshot::skip_reason(entry)) {
+                    (Some(value), None) => Line::from(vec![
+                        Span::raw(format!("  {} → ", entry.name)),
+                        Span::styled(value.to_string(), Style::default().fg(theme.highlight)),
+                    ]),
+                    (_, reason) => Line::from(Span::styled(
+                        format!("  {} - skipped: {}", entry.name, reason.unwrap_or_default()),
+                        muted,
+                    )),
+                })
+                .collect();
+            let help = format!(
+                "[y] Apply all  [n/{}] Cancel  [{}/{}] Scroll",
+                keybindings.describe_keys(Action::Cancel),
+                keybindings.describe_keys(Action::MoveUp),
+                keybindings.describe_keys(Action::MoveDown)
+            );
+            (format!(" Apply {}? ", source), lines, *scroll, help)
+        }
+        BatchState::Applying { source } => (
+            format!(" Applying {} ", source),
+            vec![Line::from(Span::styled("  Applying settings, this may take a moment...", muted))],
+            0,
+            String::new(),
+        ),
+        BatchState::Done { source, results, scroll } => {
+            let lines = results
+                .iter()
+                .map(|result| match &result.outcome {
+                    ApplyOutcome::Changed => Line::from(Span::styled(
+                        format!("  ✓ {} - changed", result.name),
+                        Style::default().fg(theme.success),
+                    )),
+                    ApplyOutcome::Unchanged => {
+                        Line::from(Span::styled(format!("  = {} - already set", result.name), muted))
+                    }
+                    ApplyOutcome::Skipped(reason) => Line::from(Span::styled(
+                        format!("  - {} - skipped: {}", result.name, reason),
+                        muted,
+                    )),
+                    ApplyOutcome::Failed(reason) => Line::from(Span::styled(
+                        format!("  ✗ {} - {}", result.name, reason),
+                        Style::default().fg(theme.error),
+                    )),
+                })
+                .collect();
+            let help = format!(
+                "[{}/{}] Close  [{}/{}] Scroll",
+                keybindings.describe_keys(Action::Select),
+                keybindings.describe_keys(Action::Cancel),
+                keybindings.describe_keys(Action::MoveUp),
+                keybindings.describe_keys(Action::MoveDown)
+            );
+            (format!(" Applied {} ", source), lines, *scroll, help)
+        }
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(theme.accent));
+    let body = Paragraph::new(lines).scroll((scroll, 0)).wrap(Wrap { trim: false });
+    let help = Paragraph::new(help).style(Style::default().fg(theme.highlight));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    f.render_widget(body, chunks[0]);
+    f.render_widget(help, chunks[1]);
+}
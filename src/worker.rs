@@ -0,0 +1,212 @@
+use std::{sync::mpsc, thread};
+use crate::{
+    app::{StagedChange, UndoRecord},
+    clipboard,
+    event::{EditorLoaded, Event},
+    network_editor::list_network_adapters,
+    settings::SettingsItem,
+    settings_editor::{option_label, DisplayModeSnapshot, SettingEditor, SettingValue},
+    profiles,
+    snapshot::{self, SnapshotEntry},
+};
+
+/// A change to a setting, run by the writer thread
+type Write = Box<dyn FnOnce(&mpsc::Sender<Event>) + Send>;
+
+/// Runs slow editor calls (powercfg, PowerShell, WMI) on background threads so
+/// the UI keeps drawing, delivering each result back as an `Event`
+#[derive(Debug, Clone)]
+pub struct Worker {
+    sender: mpsc::Sender<Event>,
+    /// Changes from the edit panel, made one at a time in the order they were asked for so
+    /// that restoring a display mode can't overtake the preview it undoes
+    writes: mpsc::Sender<Write>,
+}
+
+impl Worker {
+    pub fn new(sender: mpsc::Sender<Event>) -> Self {
+        let (writes, pending) = mpsc::channel::<Write>();
+        let writer_sender = sender.clone();
+        thread::spawn(move || {
+            for write in pending {
+                write(&writer_sender);
+            }
+        });
+        Self { sender, writes }
+    }
+    
+    fn write(&self, write: impl FnOnce(&mpsc::Sender<Event>) + Send + 'static) {
+        let _ = self.writes.send(Box::new(write));
+    }
+    
+    /// Block until every change asked for so far has been made, so quitting doesn't cut one short
+    pub fn finish_writes(&self) {
+        let (done, finished) = mpsc::channel();
+        self.write(move |_| {
+            let _ = done.send(());
+        });
+        let _ = finished.recv();
+    }
+
+    /// Fetch the current value and options an edit panel needs, answering with `Event::EditorLoaded`
+    pub fn load_editor(&self, request: u64, editor: Box<dyn SettingEditor>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            if !editor.can_read() {
+                let result = Err("The current value of this setting can't be read yet".to_string());
+                let _ = sender.send(Event::EditorLoaded(EditorLoaded { request, result }));
+                return;
+            }
+            let result = editor
+                .get_current_value()
+                .and_then(|value| Ok((value, editor.get_available_options()?)))
+                .map_err(|e| e.to_string());
+            // The app may have quit in the meantime, in which case nobody is listening
+            let _ = sender.send(Event::EditorLoaded(EditorLoaded { request, result }));
+        });
+    }
+
+    /// Read the value shown in the detail pane, answering with `Event::DetailLoaded`
+    pub fn load_detail(&self, item_name: String, editor: Box<dyn SettingEditor>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = read_label(editor.as_ref());
+            let _ = sender.send(Event::DetailLoaded { item_name, result });
+        });
+    }
+    
+    /// Read the values shown next to items in the list one after another, answering
+    /// with an `Event::DetailLoaded` per item as soon as it is read
+    pub fn load_values(&self, editors: Vec<(String, Box<dyn SettingEditor>)>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            for (item_name, editor) in editors {
+                let result = read_label(editor.as_ref());
+                if sender.send(Event::DetailLoaded { item_name, result }).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Read the current value and put it on the clipboard, answering with `Event::ValueCopied`
+    pub fn copy_value(&self, editor: Box<dyn SettingEditor>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = editor
+                .get_current_value()
+                .map(|value| value.display_with(editor.value_format()))
+                .and_then(|text| clipboard::set_text(&text).map(|_| text))
+                .map_err(|e| format!("{:#}", e));
+            let _ = sender.send(Event::ValueCopied(result));
+        });
+    }
+
+    /// Run the editor's slow check of a chosen value, answering with `Event::ValueChecked`
+    pub fn check_value(&self, request: u64, editor: Box<dyn SettingEditor>, value: SettingValue) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let warning = editor.check_value(&value);
+            let _ = sender.send(Event::ValueChecked { request, value, warning });
+        });
+    }
+
+    /// Validate and apply a value from the edit panel, answering with `Event::ValueSaved`.
+    /// With `capture`, the display mode from before is captured first so it can be put back.
+    pub fn save_value(&self, request: u64, editor: Box<dyn SettingEditor>, value: SettingValue, label: String, capture: bool) {
+        self.write(move |sender| {
+            let save = || -> anyhow::Result<Option<Box<DisplayModeSnapshot>>> {
+                if !editor.validate_value(&value)? {
+                    anyhow::bail!("{} is not a valid value", label);
+                }
+                let previous_display_mode = if capture { editor.capture_display_mode()? } else { None };
+                editor.set_value(value)?;
+                Ok(previous_display_mode.map(Box::new))
+            };
+            let result = save().map_err(|e| e.to_string());
+            let _ = sender.send(Event::ValueSaved { request, result });
+        });
+    }
+    
+    /// Write an earlier value back, answering with `Event::ChangeUndone`
+    pub fn undo_change(&self, record: UndoRecord) {
+        self.write(move |sender| {
+            let result = record.editor.set_value(record.previous_value).map_err(|e| e.to_string());
+            let _ = sender.send(Event::ChangeUndone {
+                item_name: record.item_name,
+                label: record.previous_label,
+                result,
+            });
+        });
+    }
+    
+    /// Apply a display mode while it is previewed, answering with `Event::ValuePreviewed`
+    pub fn preview_value(&self, request: u64, editor: Box<dyn SettingEditor>, value: SettingValue) {
+        self.write(move |sender| {
+            let result = editor.set_value(value.clone()).map_err(|e| e.to_string());
+            let _ = sender.send(Event::ValuePreviewed { request, value, result });
+        });
+    }
+    
+    /// Put back the display mode from before a preview, answering with `Event::PreviewRestored`
+    pub fn restore_display_mode(&self, snapshot: DisplayModeSnapshot) {
+        self.write(move |sender| {
+            let _ = sender.send(Event::PreviewRestored(snapshot.restore().map_err(|e| e.to_string())));
+        });
+    }
+
+    /// Discover the network adapters with `Get-NetAdapter`, answering with `Event::AdaptersListed`
+    pub fn list_adapters(&self) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = list_network_adapters().map_err(|e| e.to_string());
+            let _ = sender.send(Event::AdaptersListed(result));
+        });
+    }
+
+    /// Export a snapshot of every editable item, answering with `Event::SnapshotExported`
+    pub fn export_snapshot(&self, items: Vec<SettingsItem>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = snapshot::export(&items).map_err(|e| e.to_string());
+            let _ = sender.send(Event::SnapshotExported(result));
+        });
+    }
+
+    /// Apply snapshot entries one by one, answering with `Event::BatchApplied`
+    pub fn apply_snapshot(&self, entries: Vec<SnapshotEntry>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send(Event::BatchApplied(snapshot::apply(&entries)));
+        });
+    }
+
+    /// Apply changes staged in the edit panel one by one, answering with `Event::BatchApplied`
+    pub fn apply_staged(&self, changes: Vec<StagedChange>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send(Event::BatchApplied(snapshot::apply_staged(&changes)));
+        });
+    }
+    
+    /// Capture every editable item into the profile `name`, answering with `Event::ProfileCaptured`
+    pub fn capture_profile(&self, name: String, items: Vec<SettingsItem>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = profiles::capture(&name, &items)
+                .map(|count| (name, count))
+                .map_err(|e| e.to_string());
+            let _ = sender.send(Event::ProfileCaptured(result));
+        });
+    }
+}
+
+/// Current value of `editor` as the user would recognize it
+fn read_label(editor: &dyn SettingEditor) -> Result<String, String> {
+    if !editor.can_read() {
+        return Err("this setting can't be read yet".to_string());
+    }
+    let value = editor.get_current_value().map_err(|e| e.to_string())?;
+    let options = editor.get_available_options().unwrap_or_default();
+    Ok(option_label(editor, &options, &value))
+}